@@ -1,8 +1,16 @@
-use std::cmp::PartialEq;
+use std::cmp::{Ordering, PartialEq};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter::{FromIterator, IntoIterator, Iterator};
 use std::ops::Deref;
 
+use crate::classify::AddressCategory;
+use crate::equivalence::*;
+use crate::normalise::NormalisationForm;
+use crate::sanitise::escape_invisible;
+
 #[cfg(feature = "mailparse-conversions")]
 use super::error::Error;
 #[cfg(feature = "mailparse-conversions")]
@@ -16,6 +24,59 @@ pub trait DeepEq<Rhs = Self> {
     }
 }
 
+/// Find out which fields differ, the detailed counterpart of [`DeepEq`]
+///
+/// For all types in this crate, `deep_eq` holds exactly when `deep_diff`
+/// returns no differences.
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let parsed = parse_contact("Nom <adresse@example.org>").unwrap();
+/// let manual = Contact::new("adresse@example.org").set_name("Nom").set_comment("Commentaire");
+///
+/// assert_eq!(parsed.deep_diff(&manual), vec![Difference::Comment]);
+/// ```
+///
+/// [`DeepEq`]: trait.DeepEq.html
+pub trait DeepDiff<Rhs = Self> {
+    fn deep_diff(&self, other: &Rhs) -> Vec<Difference>;
+}
+
+/// A field in which two contacts, groups or address lists differ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    /// Email contact vs. garbage, or group vs. plain contacts
+    Kind,
+    /// The email addresses aren't identical
+    Email,
+    Name,
+    /// Comments differ, for garbage this is its contents
+    Comment,
+    /// The obsolete source routes differ
+    Route,
+    GroupName,
+    /// Number of contacts differs
+    Length,
+    /// The contacts at this position differ
+    Contact(usize, Vec<Difference>),
+}
+
+fn diff_contacts(this: &[Contact], other: &[Contact]) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    if this.len() != other.len() {
+        differences.push(Difference::Length);
+    }
+    for (i, (t, o)) in this.iter().zip(other).enumerate() {
+        let diff = t.deep_diff(o);
+        if !diff.is_empty() {
+            differences.push(Difference::Contact(i, diff));
+        }
+    }
+    differences
+}
+
 /// Unified interface for all contact types
 pub trait Contactish {
     fn email(&self) -> Option<&String>;
@@ -37,6 +98,16 @@ pub trait Contactish {
 }
 
 /// For everything that has contacts
+///
+/// Besides the basics, this trait provides set operations that work with any
+/// [`AddressEquivalence`]. They are hash based and keep the order of the
+/// contacts they operate on. Garbage contacts are only ever the same if their
+/// contents are identical.
+///
+/// Since `Vec<Contact>` has inherent methods called `dedup`, `retain` and
+/// `remove`, those need to be called as e.g. `Contactsish::dedup(&mut v, &Exact)`.
+///
+/// [`AddressEquivalence`]: trait.AddressEquivalence.html
 pub trait Contactsish {
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool;
@@ -44,15 +115,200 @@ pub trait Contactsish {
     fn add<C>(&mut self, contact: C)
     where
         C: Contactish;
-    fn contains(&self, contact: &Contact) -> bool;
+    fn as_contacts(&self) -> &[Contact];
+    fn as_contacts_mut(&mut self) -> &mut Vec<Contact>;
+
+    /// Check if there's a contact equal to `contact` (see [`PartialEq`])
+    ///
+    /// [`PartialEq`]: enum.Contact.html#impl-PartialEq-for-Contact
+    fn contains(&self, contact: &Contact) -> bool {
+        self.contains_with(contact, &CaseInsensitiveDomain)
+    }
+
+    /// Check if there's a contact equivalent to `contact` under `equivalence`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let list = parse_address_list("jane.doe@gmail.com").unwrap();
+    /// let contact = Contact::new("janedoe+lists@googlemail.com");
+    ///
+    /// assert!(!list.contains(&contact));
+    /// assert!(list.contains_with(&contact, &ProviderAware));
+    /// ```
+    fn contains_with<E>(&self, contact: &Contact, equivalence: &E) -> bool
+    where
+        E: AddressEquivalence + ?Sized,
+    {
+        let key = ContactKey::new(contact, equivalence);
+        self.as_contacts()
+            .iter()
+            .any(|c| ContactKey::new(c, equivalence) == key)
+    }
+
+    /// Remove all but the first of equivalent contacts
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let mut list = parse_address_list(
+    ///     "Ainm <seoladh@example.org>, seoladh@EXAMPLE.org, eile@example.org"
+    /// ).unwrap();
+    ///
+    /// list.dedup(&CaseInsensitiveDomain);
+    ///
+    /// assert!(list.deep_eq(&vec![
+    ///     Contact::new("seoladh@example.org").set_name("Ainm"),
+    ///     Contact::new("eile@example.org"),
+    /// ].into()));
+    /// ```
+    fn dedup<E>(&mut self, equivalence: &E)
+    where
+        E: AddressEquivalence + ?Sized,
+    {
+        let mut seen = HashSet::new();
+        self.as_contacts_mut()
+            .retain(|c| seen.insert(ContactKey::new(c, equivalence)));
+    }
+
+    /// Only keep contacts for which `keep` returns `true`
+    fn retain<F>(&mut self, keep: F)
+    where
+        F: FnMut(&Contact) -> bool,
+    {
+        self.as_contacts_mut().retain(keep);
+    }
+
+    /// Remove all contacts equivalent to `contact`, returns whether there
+    /// were any
+    fn remove<E>(&mut self, contact: &Contact, equivalence: &E) -> bool
+    where
+        E: AddressEquivalence + ?Sized,
+    {
+        let key = ContactKey::new(contact, equivalence);
+        let contacts = self.as_contacts_mut();
+        let len = contacts.len();
+        contacts.retain(|c| ContactKey::new(c, equivalence) != key);
+        contacts.len() != len
+    }
+
+    /// Append all contacts of `other` that aren't in here yet
+    fn union<T, E>(&mut self, other: &T, equivalence: &E)
+    where
+        T: Contactsish + ?Sized,
+        E: AddressEquivalence + ?Sized,
+    {
+        let contacts = self.as_contacts_mut();
+        let mut seen: HashSet<_> = contacts
+            .iter()
+            .map(|c| ContactKey::new(c, equivalence))
+            .collect();
+        for contact in other.as_contacts() {
+            if seen.insert(ContactKey::new(contact, equivalence)) {
+                contacts.push(contact.clone());
+            }
+        }
+    }
+
+    /// Only keep contacts that are also in `other`
+    fn intersection<T, E>(&mut self, other: &T, equivalence: &E)
+    where
+        T: Contactsish + ?Sized,
+        E: AddressEquivalence + ?Sized,
+    {
+        let keys = contact_keys(other.as_contacts(), equivalence);
+        self.as_contacts_mut()
+            .retain(|c| keys.contains(&ContactKey::new(c, equivalence)));
+    }
+
+    /// Remove all contacts that are in `other`
+    fn difference<T, E>(&mut self, other: &T, equivalence: &E)
+    where
+        T: Contactsish + ?Sized,
+        E: AddressEquivalence + ?Sized,
+    {
+        let keys = contact_keys(other.as_contacts(), equivalence);
+        self.as_contacts_mut()
+            .retain(|c| !keys.contains(&ContactKey::new(c, equivalence)));
+    }
+
+    /// Like [`union`], but if a contact in here has no name and its
+    /// equivalent in `other` does, take over name and comment from `other`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let mut list = parse_address_list("nimi@example.org").unwrap();
+    /// let other = parse_address_list("Nimi <nimi@example.org>, toinen@example.org").unwrap();
+    ///
+    /// list.merge_preferring_names(&other, &Exact);
+    ///
+    /// assert!(list.deep_eq(&vec![
+    ///     Contact::new("nimi@example.org").set_name("Nimi"),
+    ///     Contact::new("toinen@example.org"),
+    /// ].into()));
+    /// ```
+    ///
+    /// [`union`]: #method.union
+    fn merge_preferring_names<T, E>(&mut self, other: &T, equivalence: &E)
+    where
+        T: Contactsish + ?Sized,
+        E: AddressEquivalence + ?Sized,
+    {
+        let contacts = self.as_contacts_mut();
+        let mut index: HashMap<_, _> = contacts
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, c)| (ContactKey::new(c, equivalence), i))
+            .collect();
+        for contact in other.as_contacts() {
+            match index.entry(ContactKey::new(contact, equivalence)) {
+                Entry::Occupied(i) => {
+                    let existing = &mut contacts[*i.get()];
+                    if existing.name().is_none() {
+                        if let Some(name) = contact.name() {
+                            let mut named = existing.clone().set_name(name);
+                            if let Some(comment) = contact.comment() {
+                                named = named.set_comment(comment);
+                            }
+                            *existing = named;
+                        }
+                    }
+                }
+                Entry::Vacant(v) => {
+                    v.insert(contacts.len());
+                    contacts.push(contact.clone());
+                }
+            }
+        }
+    }
+}
+
+fn contact_keys<E>(contacts: &[Contact], equivalence: &E) -> HashSet<ContactKey>
+where
+    E: AddressEquivalence + ?Sized,
+{
+    contacts
+        .iter()
+        .map(|c| ContactKey::new(c, equivalence))
+        .collect()
 }
 
 /// A contact with at least an email address
 #[derive(Debug, Clone, Default)]
 pub struct EmailContact {
-    email: String,
-    name: Option<String>,
-    comment: Option<String>,
+    pub(crate) email: String,
+    pub(crate) name: Option<String>,
+    pub(crate) comment: Option<String>,
+    route: Vec<String>,
+    deobfuscated: bool,
+    pub(crate) name_inferred: bool,
+    pub(crate) categories: Vec<AddressCategory>,
+    pub(crate) normalisation: Option<NormalisationForm>,
 }
 
 impl Contactish for EmailContact {
@@ -76,6 +332,11 @@ impl Contactish for EmailContact {
             email: email.as_ref().into(),
             name: None,
             comment: None,
+            route: Vec::new(),
+            deobfuscated: false,
+            name_inferred: false,
+            categories: Vec::new(),
+            normalisation: None,
         }
     }
 
@@ -86,6 +347,7 @@ impl Contactish for EmailContact {
         let name = name.as_ref().trim();
         if !name.is_empty() {
             self.name = Some(name.into());
+            self.name_inferred = false;
         }
         self
     }
@@ -114,10 +376,114 @@ impl Contactish for EmailContact {
     }
 }
 
-/// Check if the email field is the same
+/// Check if the email address is the same
+///
+/// The domain is compared case-insensitively, the local part is compared as
+/// is, since only the receiving server knows how to interpret it (see
+/// [`CaseInsensitiveDomain`]). Name and comment are ignored, use
+/// [`DeepEq`] to compare those too.
+///
+/// [`CaseInsensitiveDomain`]: struct.CaseInsensitiveDomain.html
+/// [`DeepEq`]: trait.DeepEq.html
 impl PartialEq for EmailContact {
     fn eq(&self, other: &EmailContact) -> bool {
-        self.email == other.email
+        self.canonical_email() == other.canonical_email()
+    }
+}
+
+impl Eq for EmailContact {}
+
+/// Consistent with [`PartialEq`], only the canonical address is hashed
+///
+/// [`PartialEq`]: #impl-PartialEq-for-EmailContact
+impl Hash for EmailContact {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_email().hash(state);
+    }
+}
+
+/// Ordered by canonical address, i.e. the local part followed by the
+/// lower-cased domain, compared code point by code point
+impl Ord for EmailContact {
+    fn cmp(&self, other: &EmailContact) -> Ordering {
+        self.canonical_email().cmp(&other.canonical_email())
+    }
+}
+
+impl PartialOrd for EmailContact {
+    fn partial_cmp(&self, other: &EmailContact) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl EmailContact {
+    /// The address the way it's used for comparison, hashing and ordering:
+    /// with the domain lower-cased and the local part untouched
+    pub fn canonical_email(&self) -> String {
+        CaseInsensitiveDomain.key(&self.email)
+    }
+
+    /// Hops of an obsolete source route like `<@relay1,@relay2:user@example.org>`
+    ///
+    /// RFC 5322 says these should be ignored, but they are kept around (and
+    /// displayed) so they aren't lost on messages from old archives.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let list = parse_address_list("Ainm <@relay1,@relay2.example:user@example.org>").unwrap();
+    /// let contact = match &list.contacts()[0] {
+    ///     Contact::Email(e) => e.clone(),
+    ///     Contact::Garbage(_) => unreachable!(),
+    /// };
+    ///
+    /// assert_eq!(contact.route(), ["relay1", "relay2.example"]);
+    /// assert_eq!(contact.to_string(), r#""Ainm" <@relay1,@relay2.example:user@example.org>"#);
+    ///
+    /// let contact = contact.strip_route();
+    /// assert_eq!(contact.to_string(), r#""Ainm" <user@example.org>"#);
+    /// ```
+    pub fn route(&self) -> &[String] {
+        &self.route
+    }
+
+    pub fn set_route<I, T>(mut self, hops: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.route = hops.into_iter().map(|h| h.as_ref().into()).collect();
+        self
+    }
+
+    /// Drop the source route, as RFC 5322 instructs
+    pub fn strip_route(mut self) -> Self {
+        self.route.clear();
+        self
+    }
+
+    /// Whether the address had to be de-obfuscated to be found
+    ///
+    /// This is about where the contact came from rather than what it is, so
+    /// it's ignored by [`DeepEq`].
+    ///
+    /// [`DeepEq`]: trait.DeepEq.html
+    pub fn is_deobfuscated(&self) -> bool {
+        self.deobfuscated
+    }
+
+    pub fn set_deobfuscated(mut self, deobfuscated: bool) -> Self {
+        self.deobfuscated = deobfuscated;
+        self
+    }
+
+    /// Like `==`, but with a different notion of which addresses are the same
+    pub fn eq_with<E>(&self, other: &EmailContact, equivalence: &E) -> bool
+    where
+        E: AddressEquivalence + ?Sized,
+    {
+        equivalence.equivalent(&self.email, &other.email)
     }
 }
 
@@ -125,22 +491,57 @@ impl PartialEq for EmailContact {
 /// same)
 impl DeepEq for EmailContact {
     fn deep_eq(&self, other: &EmailContact) -> bool {
-        self.email == other.email && self.name == other.name && self.comment == other.comment
+        self.deep_diff(other).is_empty()
+    }
+}
+
+impl DeepDiff for EmailContact {
+    fn deep_diff(&self, other: &EmailContact) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        if self.email != other.email {
+            differences.push(Difference::Email);
+        }
+        if self.name != other.name {
+            differences.push(Difference::Name);
+        }
+        if self.comment != other.comment {
+            differences.push(Difference::Comment);
+        }
+        if self.route != other.route {
+            differences.push(Difference::Route);
+        }
+        differences
     }
 }
 
 impl fmt::Display for EmailContact {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let safe = f.alternate();
+        let text = |t: &str| {
+            if safe {
+                escape_invisible(t)
+            } else {
+                t.to_string()
+            }
+        };
         if let Some(n) = &self.name {
-            write!(f, "\"{}\" ", n.replace('\\', "\\\\").replace('"', "\\\""))?;
+            write!(
+                f,
+                "\"{}\" ",
+                text(&n.replace('\\', "\\\\").replace('"', "\\\""))
+            )?;
             if let Some(c) = &self.comment {
-                write!(f, "({}) ", c)?;
+                write!(f, "({}) ", text(c))?;
             }
         }
+        write!(f, "<")?;
+        if !self.route.is_empty() {
+            write!(f, "@{}:", text(&self.route.join(",@")))?;
+        }
         write!(
             f,
-            "<{}>",
-            self.email.replace('\\', "\\\\").replace('"', "\\\""),
+            "{}>",
+            text(&self.email.replace('\\', "\\\\").replace('"', "\\\"")),
         )
     }
 }
@@ -150,7 +551,7 @@ impl fmt::Display for EmailContact {
 ///
 /// [`EmailContact`]: struct.EmailContact.html
 /// [`Contactish`]: trait.Contactish.html
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct GarbageContact(String);
 
 impl Contactish for GarbageContact {
@@ -220,6 +621,16 @@ impl Contact {
     pub fn is_garbage(&self) -> bool {
         matches!(self, Contact::Garbage(_))
     }
+
+    /// Like `==`, but with a different notion of which addresses are the same
+    ///
+    /// Garbage is still only equal to identical garbage.
+    pub fn eq_with<E>(&self, other: &Contact, equivalence: &E) -> bool
+    where
+        E: AddressEquivalence + ?Sized,
+    {
+        ContactKey::new(self, equivalence) == ContactKey::new(other, equivalence)
+    }
 }
 
 /// Will be handed down on our variants' contents, which implement the same
@@ -298,17 +709,64 @@ impl Contactish for Contact {
     }
 }
 
+/// Email contacts are equal if their canonical addresses are (see
+/// [`EmailContact`]), garbage is equal if its contents are identical, and
+/// email contacts are never equal to garbage
+///
+/// [`EmailContact`]: struct.EmailContact.html#impl-PartialEq-for-EmailContact
 impl PartialEq for Contact {
     fn eq(&self, other: &Contact) -> bool {
-        self.email() == other.email()
+        match (self, other) {
+            (Contact::Email(s), Contact::Email(o)) => s == o,
+            (Contact::Garbage(s), Contact::Garbage(o)) => s == o,
+            _ => false,
+        }
     }
 }
 
+impl Eq for Contact {}
+
+impl Hash for Contact {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ContactKey::new(self, &CaseInsensitiveDomain).hash(state);
+    }
+}
+
+/// Total ordering for stable sorting: email contacts come first, ordered by
+/// their canonical address, followed by garbage, ordered by its contents
+impl Ord for Contact {
+    fn cmp(&self, other: &Contact) -> Ordering {
+        match (self, other) {
+            (Contact::Email(s), Contact::Email(o)) => s.cmp(o),
+            (Contact::Garbage(s), Contact::Garbage(o)) => s.cmp(o),
+            (Contact::Email(_), Contact::Garbage(_)) => Ordering::Less,
+            (Contact::Garbage(_), Contact::Email(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for Contact {
+    fn partial_cmp(&self, other: &Contact) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// All fields need to be identical, an email contact is never deep equal to
+/// garbage
 impl DeepEq for Contact {
     fn deep_eq(&self, other: &Contact) -> bool {
-        self.email() == other.email()
-            || self.name() == other.name()
-            || self.comment() == other.comment()
+        self.deep_diff(other).is_empty()
+    }
+}
+
+impl DeepDiff for Contact {
+    fn deep_diff(&self, other: &Contact) -> Vec<Difference> {
+        match (self, other) {
+            (Contact::Email(s), Contact::Email(o)) => s.deep_diff(o),
+            (Contact::Garbage(s), Contact::Garbage(o)) if s == o => vec![],
+            (Contact::Garbage(_), Contact::Garbage(_)) => vec![Difference::Comment],
+            _ => vec![Difference::Kind],
+        }
     }
 }
 
@@ -343,7 +801,7 @@ impl fmt::Display for Contact {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Contact::Garbage(_) => write!(f, ""),
-            Contact::Email(e) => write!(f, "{}", e),
+            Contact::Email(e) => fmt::Display::fmt(e, f),
         }
     }
 }
@@ -428,8 +886,12 @@ impl Contactsish for Vec<Contact> {
         self.push(contact.to_contact());
     }
 
-    fn contains(&self, contact: &Contact) -> bool {
-        self.iter().any(|y| y == contact)
+    fn as_contacts(&self) -> &[Contact] {
+        self
+    }
+
+    fn as_contacts_mut(&mut self) -> &mut Vec<Contact> {
+        self
     }
 }
 
@@ -453,8 +915,12 @@ impl Contactsish for Contacts {
         self.contacts.push(contact.to_contact());
     }
 
-    fn contains(&self, contact: &Contact) -> bool {
-        self.contacts.contains(contact)
+    fn as_contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    fn as_contacts_mut(&mut self) -> &mut Vec<Contact> {
+        &mut self.contacts
     }
 }
 
@@ -506,7 +972,11 @@ impl fmt::Display for Contacts {
             "{}",
             self.contacts
                 .iter()
-                .map(|c| format!("{}", c))
+                .map(|c| if f.alternate() {
+                    format!("{:#}", c)
+                } else {
+                    format!("{}", c)
+                })
                 .collect::<Vec<String>>()
                 .join(", ")
                 .trim_matches(trim),
@@ -566,17 +1036,22 @@ impl PartialEq for Group {
     }
 }
 
+impl Eq for Group {}
+
 impl DeepEq for Group {
     fn deep_eq(&self, other: &Group) -> bool {
-        if self.name != other.name || self.contacts.len() != other.contacts.len() {
-            return false;
-        }
-        for (i, contact) in self.contacts.iter().enumerate() {
-            if !contact.deep_eq(&other.contacts[i]) {
-                return false;
-            }
+        self.deep_diff(other).is_empty()
+    }
+}
+
+impl DeepDiff for Group {
+    fn deep_diff(&self, other: &Group) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        if self.name != other.name {
+            differences.push(Difference::GroupName);
         }
-        true
+        differences.extend(diff_contacts(&self.contacts, &other.contacts));
+        differences
     }
 }
 
@@ -612,8 +1087,12 @@ impl Contactsish for Group {
         self.contacts.add(contact.to_contact());
     }
 
-    fn contains(&self, contact: &Contact) -> bool {
-        self.contacts.contains(contact)
+    fn as_contacts(&self) -> &[Contact] {
+        &self.contacts.contacts
+    }
+
+    fn as_contacts_mut(&mut self) -> &mut Vec<Contact> {
+        &mut self.contacts.contacts
     }
 }
 
@@ -635,12 +1114,12 @@ impl TryInto<mailparse::MailAddr> for Group {
 
 impl fmt::Display for Group {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "\"{}\": {};",
-            self.name.replace('\\', "\\\\").replace('"', "\\\""),
-            self.contacts
-        )
+        let name = self.name.replace('\\', "\\\\").replace('"', "\\\"");
+        if f.alternate() {
+            write!(f, "\"{}\": {:#};", escape_invisible(&name), self.contacts)
+        } else {
+            write!(f, "\"{}\": {};", name, self.contacts)
+        }
     }
 }
 
@@ -685,11 +1164,29 @@ impl AddressList {
     }
 }
 
+impl AddressList {
+    /// Drop the source routes of all contacts
+    pub fn strip_routes(&mut self) {
+        for contact in self.as_contacts_mut() {
+            if let Contact::Email(e) = contact {
+                e.route.clear();
+            }
+        }
+    }
+}
+
+/// Formats the list for use in a header
+///
+/// The alternate form (`{:#}`) is meant for showing the list to people:
+/// invisible and bidirectional control characters are escaped, see
+/// [`is_invisible`].
+///
+/// [`is_invisible`]: fn.is_invisible.html
 impl fmt::Display for AddressList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            AddressList::Contacts(c) => write!(f, "{}", c),
-            AddressList::Group(g) => write!(f, "{}", g),
+            AddressList::Contacts(c) => fmt::Display::fmt(c, f),
+            AddressList::Group(g) => fmt::Display::fmt(g, f),
         }
     }
 }
@@ -726,34 +1223,20 @@ impl PartialEq for AddressList {
     }
 }
 
+impl Eq for AddressList {}
+
 impl DeepEq for AddressList {
     fn deep_eq(&self, other: &AddressList) -> bool {
-        if self.is_group() != other.is_group() {
-            return false;
-        }
-        match self {
-            AddressList::Group(g) => {
-                if let AddressList::Group(o) = other {
-                    g.deep_eq(o)
-                } else {
-                    false
-                }
-            }
-            AddressList::Contacts(c) => {
-                if let AddressList::Contacts(o) = other {
-                    if c.len() != o.len() {
-                        return false;
-                    }
-                    for (i, contact) in c.iter().enumerate() {
-                        if !contact.deep_eq(&o[i]) {
-                            return false;
-                        }
-                    }
-                    true
-                } else {
-                    false
-                }
-            }
+        self.deep_diff(other).is_empty()
+    }
+}
+
+impl DeepDiff for AddressList {
+    fn deep_diff(&self, other: &AddressList) -> Vec<Difference> {
+        match (self, other) {
+            (AddressList::Group(s), AddressList::Group(o)) => s.deep_diff(o),
+            (AddressList::Contacts(s), AddressList::Contacts(o)) => diff_contacts(s, o),
+            _ => vec![Difference::Kind],
         }
     }
 }
@@ -796,10 +1279,17 @@ impl Contactsish for AddressList {
         }
     }
 
-    fn contains(&self, contact: &Contact) -> bool {
+    fn as_contacts(&self) -> &[Contact] {
+        match self {
+            Self::Contacts(c) => c.as_contacts(),
+            Self::Group(g) => g.as_contacts(),
+        }
+    }
+
+    fn as_contacts_mut(&mut self) -> &mut Vec<Contact> {
         match self {
-            Self::Contacts(c) => c.contains(contact),
-            Self::Group(g) => g.contains(contact),
+            Self::Contacts(c) => c.as_contacts_mut(),
+            Self::Group(g) => g.as_contacts_mut(),
         }
     }
 }
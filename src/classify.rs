@@ -0,0 +1,261 @@
+use lazy_static::*;
+use regex::Regex;
+
+use crate::address_list::*;
+use crate::error::{Error, Result};
+
+/// Local parts of shared mailboxes, which don't belong to a single person
+pub(crate) const ROLE_ACCOUNTS: &[&str] = &[
+    "abuse",
+    "accounts",
+    "admin",
+    "administrator",
+    "billing",
+    "contact",
+    "enquiries",
+    "feedback",
+    "hello",
+    "help",
+    "hostmaster",
+    "hr",
+    "info",
+    "jobs",
+    "mail",
+    "marketing",
+    "news",
+    "newsletter",
+    "noc",
+    "notifications",
+    "office",
+    "postmaster",
+    "press",
+    "privacy",
+    "root",
+    "sales",
+    "security",
+    "service",
+    "support",
+    "team",
+    "webmaster",
+];
+
+lazy_static! {
+    static ref BUILTIN: ClassificationRules = ClassificationRules::default();
+}
+
+/// What kind of mailbox an address belongs to, if not a person's
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AddressCategory {
+    /// Replies aren't read, like `noreply@` or `do-not-reply@`
+    NoReply,
+    /// A shared mailbox like `postmaster@`, `abuse@` or `support@`
+    RoleAccount,
+    /// Where bounces go, like `mailer-daemon@` or a VERP address like
+    /// `bounces+jane=example.org@lists.example.org`
+    Bounce,
+    /// A mailing list or one of its administrative addresses
+    MailingList,
+}
+
+/// A regular expression that puts the addresses it matches into a category
+///
+/// Patterns are matched against the whole address, with the local part
+/// unquoted and everything lower-cased.
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let rule = ClassificationRule::new(AddressCategory::NoReply, "^alerts@").unwrap();
+/// let rules = ClassificationRules::default().set_rule(rule);
+///
+/// assert_eq!(
+///     EmailContact::new("Alerts@example.org").classify_with(&rules),
+///     vec![AddressCategory::NoReply],
+/// );
+/// assert!(ClassificationRule::new(AddressCategory::NoReply, "(").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClassificationRule {
+    category: AddressCategory,
+    pattern: Regex,
+}
+
+impl ClassificationRule {
+    pub fn new(category: AddressCategory, pattern: &str) -> Result<Self> {
+        let pattern = Regex::new(pattern).map_err(|e| Error::InvalidPattern(e.to_string()))?;
+        Ok(Self { category, pattern })
+    }
+
+    pub fn category(&self) -> AddressCategory {
+        self.category
+    }
+
+    pub fn pattern(&self) -> &str {
+        self.pattern.as_str()
+    }
+}
+
+/// A table of [`ClassificationRule`]s
+///
+/// The [`Default`] table recognises common no-reply, role account, bounce
+/// and mailing list addresses. Users can add their own rules on top.
+///
+/// [`ClassificationRule`]: struct.ClassificationRule.html
+/// [`Default`]: #impl-Default-for-ClassificationRules
+#[derive(Debug, Clone)]
+pub struct ClassificationRules {
+    rules: Vec<ClassificationRule>,
+}
+
+impl ClassificationRules {
+    /// An empty table
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    pub fn add(&mut self, rule: ClassificationRule) {
+        self.rules.push(rule);
+    }
+
+    /// Builder style version of [`add`]
+    ///
+    /// [`add`]: #method.add
+    pub fn set_rule(mut self, rule: ClassificationRule) -> Self {
+        self.add(rule);
+        self
+    }
+
+    /// Categories of all rules matching `contact`, sorted and without
+    /// duplicates
+    pub fn classify(&self, contact: &EmailContact) -> Vec<AddressCategory> {
+        let address = match (contact.local_part_unquoted(), contact.domain()) {
+            (Some(local), Some(domain)) => format!("{}@{}", local, domain).to_lowercase(),
+            _ => contact.email().unwrap().to_lowercase(),
+        };
+        let mut categories: Vec<_> = self
+            .rules
+            .iter()
+            .filter(|r| r.pattern.is_match(&address))
+            .map(|r| r.category)
+            .collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+}
+
+impl Default for ClassificationRules {
+    fn default() -> Self {
+        let roles = format!("^(?:{})@", ROLE_ACCOUNTS.join("|"));
+        [
+            (
+                AddressCategory::NoReply,
+                r"^[^@]*(?:no|do[-_.]?not)[-_.]?reply\b",
+            ),
+            (AddressCategory::RoleAccount, roles.as_str()),
+            (AddressCategory::Bounce, r"^mailer[-_]?daemon@"),
+            (
+                AddressCategory::Bounce,
+                r"^(?:bounces?|return)(?:[-+=._][^@]*)?@",
+            ),
+            (AddressCategory::Bounce, r"^[^@]+-bounces(?:[-+=._][^@]*)?@"),
+            (AddressCategory::Bounce, r"^[^@]+[-+][^@]*=[^@]+@"),
+            (AddressCategory::Bounce, r"^prvs=[^@]+@"),
+            (
+                AddressCategory::MailingList,
+                r"^[^@]+-(?:request|owner|join|leave|subscribe|unsubscribe)@",
+            ),
+            (AddressCategory::MailingList, r"^owner-[^@]+@"),
+            (
+                AddressCategory::MailingList,
+                r"^(?:majordomo|listserv|mailman|sympa|ezmlm)@",
+            ),
+            (
+                AddressCategory::MailingList,
+                r"@(?:lists?\.|groups\.|googlegroups\.com$)",
+            ),
+        ]
+        .into_iter()
+        .fold(ClassificationRules::new(), |rules, (category, pattern)| {
+            rules.set_rule(ClassificationRule::new(category, pattern).unwrap())
+        })
+    }
+}
+
+impl EmailContact {
+    /// Categories of this address according to the built-in
+    /// [`ClassificationRules`], empty for what looks like a person's address
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let categories = |email| EmailContact::new(email).classify();
+    ///
+    /// assert_eq!(categories("noreply@example.org"), vec![AddressCategory::NoReply]);
+    /// assert_eq!(categories("MAILER-DAEMON@example.org"), vec![AddressCategory::Bounce]);
+    /// assert_eq!(
+    ///     categories("dev-bounces+jane=example.org@lists.example.org"),
+    ///     vec![AddressCategory::Bounce, AddressCategory::MailingList],
+    /// );
+    /// assert!(categories("jane@example.org").is_empty());
+    /// ```
+    ///
+    /// [`ClassificationRules`]: struct.ClassificationRules.html
+    pub fn classify(&self) -> Vec<AddressCategory> {
+        BUILTIN.classify(self)
+    }
+
+    /// Like [`classify`], but with custom rules
+    ///
+    /// [`classify`]: #method.classify
+    pub fn classify_with(&self, rules: &ClassificationRules) -> Vec<AddressCategory> {
+        rules.classify(self)
+    }
+
+    /// Categories stored by [`AddressList::mark_classified`]
+    ///
+    /// Like [`is_deobfuscated`], this is ignored by [`DeepEq`].
+    ///
+    /// [`AddressList::mark_classified`]: enum.AddressList.html#method.mark_classified
+    /// [`is_deobfuscated`]: #method.is_deobfuscated
+    /// [`DeepEq`]: trait.DeepEq.html
+    pub fn marked_categories(&self) -> &[AddressCategory] {
+        &self.categories
+    }
+}
+
+impl AddressList {
+    /// Remove contacts in any of `categories`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let mut list = parse_address_list(
+    ///     "noreply@example.org, Jane <jane@example.org>, postmaster@example.org",
+    /// ).unwrap();
+    /// list.drop_classified(&ClassificationRules::default(), &[AddressCategory::NoReply]);
+    ///
+    /// assert_eq!(list.to_string(), r#""Jane" <jane@example.org>, <postmaster@example.org>"#);
+    /// ```
+    pub fn drop_classified(&mut self, rules: &ClassificationRules, categories: &[AddressCategory]) {
+        self.retain(|c| match c {
+            Contact::Email(e) => !rules.classify(e).iter().any(|c| categories.contains(c)),
+            Contact::Garbage(_) => true,
+        });
+    }
+
+    /// Store the categories of all contacts, see
+    /// [`EmailContact::marked_categories`]
+    ///
+    /// [`EmailContact::marked_categories`]: struct.EmailContact.html#method.marked_categories
+    pub fn mark_classified(&mut self, rules: &ClassificationRules) {
+        for contact in self.as_contacts_mut() {
+            if let Contact::Email(e) = contact {
+                e.categories = rules.classify(e);
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use lazy_static::*;
+use regex::{Captures, Regex};
+use std::ops::Range;
+
+/// Words for "at" in a few languages
+const AT: &[&str] = &[
+    "at",
+    "ät",
+    "arobase",
+    "arroba",
+    "chiocciola",
+    "apenstaartje",
+    "snabel-a",
+    "małpa",
+    "zavináč",
+    "kukac",
+    "собака",
+    "παπάκι",
+];
+
+/// Words for "dot" in a few languages
+const DOT: &[&str] = &[
+    "dot",
+    "punkt",
+    "point",
+    "punto",
+    "ponto",
+    "punt",
+    "tečka",
+    "pont",
+    "piste",
+    "точка",
+    "τελεία",
+];
+
+/// A word either surrounded by whitespace or in brackets, e.g. ` at `,
+/// `[at]` or `(at)`
+fn token(words: &[&str]) -> String {
+    let words = words.join("|");
+    format!(
+        r"(?:\s+(?:{0})\s+|\s*[\[({{]\s*(?:{0})\s*[\])}}]\s*)",
+        words
+    )
+}
+
+lazy_static! {
+    pub(crate) static ref OBFUSCATED: Regex = Regex::new(&format!(
+        r"(?i)\b([\w.+-]+){}([\w-]+(?:(?:{}|\.)[\w-]+)+)\b",
+        token(AT),
+        token(DOT),
+    ))
+    .unwrap();
+    static ref DOT_TOKEN: Regex = Regex::new(&format!("(?i){}", token(DOT))).unwrap();
+    // Only a label in front of at least two more, so it's never the
+    // registrable one
+    static ref SPAM_DOMAIN: Regex = Regex::new(
+        r"(?i)([@.])(?:no[._-]?spam|remove[._-]?(?:this|me))\.([\w-]+\.[\w-])"
+    )
+    .unwrap();
+    // Only a suffix of the local part, never all of it
+    static ref SPAM_LOCAL: Regex =
+        Regex::new(r"(?i)([\w+])[._-](?:no[._-]?spam|remove[._-]?(?:this|me))@").unwrap();
+}
+
+/// The address hidden in a match of `OBFUSCATED`
+pub(crate) fn address(captures: &Captures<'_>) -> String {
+    format!(
+        "{}@{}",
+        &captures[1],
+        DOT_TOKEN.replace_all(&captures[2], ".")
+    )
+}
+
+/// Whether the match `span` of `OBFUSCATED` in the address list `input` is
+/// where an address would go
+///
+/// Quoted strings, display names and `<…>` that already hold an address are
+/// off limits. A plain ` at ` with literal dots is common in prose, so it
+/// only counts if it makes up the whole list item or `<…>`.
+fn in_address_position(input: &str, span: Range<usize>, plain: bool) -> bool {
+    let (mut quoted, mut escaped) = (false, false);
+    let mut angle = None;
+    let mut item = 0;
+    for (i, c) in input[..span.start].char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '<' if !quoted => angle = Some(i + 1),
+            '>' if !quoted => angle = None,
+            ',' | ';' | ':' if !quoted && angle.is_none() => item = i + 1,
+            _ => {}
+        }
+    }
+    if quoted {
+        return false;
+    }
+    let rest = &input[span.end..];
+    let slot = match angle {
+        Some(start) => start..span.end + rest.find('>').unwrap_or(rest.len()),
+        None => {
+            let end = span.end + rest.find([',', ';', '<']).unwrap_or(rest.len());
+            if input[end..].starts_with('<') {
+                // A display name
+                return false;
+            }
+            item..end
+        }
+    };
+    let slot = &input[slot];
+    !slot.contains('@') && (!plain || slot.trim() == input[span].trim())
+}
+
+/// Undo common ways of hiding addresses from harvesters in an address list
+///
+/// Recognises "at" and "dot" spelled out between whitespace or in brackets
+/// (`user [at] example (dot) org`, `user at example dot org`), in several
+/// languages, as well as `NOSPAM` and `REMOVETHIS` markers inside addresses.
+/// Display names and quoted strings are left alone, and so is everything
+/// else.
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// assert_eq!(deobfuscate("user [at] example [dot] org"), "user@example.org");
+/// assert_eq!(deobfuscate("Nom <nom arobase exemple point fr>"), "Nom <nom@exemple.fr>");
+/// assert_eq!(deobfuscate("user@NOSPAM.example.org"), "user@example.org");
+/// assert_eq!(deobfuscate("nospam@example.org"), "nospam@example.org");
+/// assert_eq!(deobfuscate("Meet me at home.com"), "Meet me at home.com");
+/// ```
+pub fn deobfuscate(input: &str) -> String {
+    deobfuscate_tracked(input).0
+}
+
+/// Replace the matches of `regex` in `text` that `replace` returns something
+/// for, keeping track of which byte ranges hold rewritten addresses
+///
+/// Existing `spans` are moved along with the text, the addresses around new
+/// replacements are added.
+fn rewrite<F>(text: &str, regex: &Regex, spans: &mut Vec<Range<usize>>, mut replace: F) -> String
+where
+    F: FnMut(&Captures<'_>) -> Option<String>,
+{
+    let mut output = String::with_capacity(text.len());
+    let mut edits: Vec<(Range<usize>, Range<usize>)> = Vec::new();
+    let mut last = 0;
+    for c in regex.captures_iter(text) {
+        let whole = c.get(0).unwrap();
+        if let Some(replacement) = replace(&c) {
+            output.push_str(&text[last..whole.start()]);
+            let start = output.len();
+            output.push_str(&replacement);
+            edits.push((whole.range(), start..output.len()));
+            last = whole.end();
+        }
+    }
+    output.push_str(&text[last..]);
+
+    // Rewrites only ever happen inside an address, so a position moves by
+    // whatever the edits before it added or removed
+    let moved = |position: usize| {
+        edits
+            .iter()
+            .filter(|(old, _)| old.end <= position)
+            .fold(position as isize, |p, (old, new)| {
+                p + new.len() as isize - old.len() as isize
+            }) as usize
+    };
+    for span in spans.iter_mut() {
+        *span = moved(span.start)..moved(span.end);
+    }
+    let delimiter = |c: char| c.is_whitespace() || "<>()[]{},;:\"".contains(c);
+    for (_, new) in edits {
+        let start = output[..new.start]
+            .rfind(delimiter)
+            .map_or(0, |i| i + output[i..].chars().next().unwrap().len_utf8());
+        let end = output[new.end..]
+            .find(delimiter)
+            .map_or(output.len(), |i| new.end + i);
+        if !spans.iter().any(|s| s.start <= start && end <= s.end) {
+            spans.push(start..end);
+        }
+    }
+    spans.sort_by_key(|s| s.start);
+    output
+}
+
+/// Like [`deobfuscate`], also returning the byte ranges of the addresses in
+/// the output that were rewritten
+pub(crate) fn deobfuscate_tracked(input: &str) -> (String, Vec<Range<usize>>) {
+    let mut spans = Vec::new();
+    let output = rewrite(input, &OBFUSCATED, &mut spans, |c| {
+        let whole = c.get(0).unwrap();
+        let at = &input[c.get(1).unwrap().end()..c.get(2).unwrap().start()];
+        let plain = !at.contains(['[', '(', '{']) && !DOT_TOKEN.is_match(&c[2]);
+        in_address_position(input, whole.range(), plain).then(|| address(c))
+    });
+    let output = rewrite(&output, &SPAM_DOMAIN, &mut spans, |c| {
+        Some(format!("{}{}", &c[1], &c[2]))
+    });
+    let output = rewrite(&output, &SPAM_LOCAL, &mut spans, |c| {
+        Some(format!("{}@", &c[1]))
+    });
+    (output, spans)
+}
@@ -0,0 +1,216 @@
+use lazy_static::*;
+use regex::Regex;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::address_list::*;
+use crate::error::*;
+
+lazy_static! {
+    // RFC 5321 General-address-literal: Standardized-tag ":" 1*dcontent
+    static ref GENERAL_LITERAL: Regex =
+        Regex::new(r"^([a-zA-Z0-9-]*[a-zA-Z0-9]):([!-Z^-~]+)$").unwrap();
+}
+
+/// The contents of a domain literal like `[192.0.2.1]`, as defined by
+/// RFC 5321
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressLiteral {
+    /// `[192.0.2.1]` or `[IPv6:2001:db8::1]`
+    Ip(IpAddr),
+    /// Any other `[tag:content]` literal
+    General { tag: String, content: String },
+}
+
+impl AddressLiteral {
+    /// Interpret a bracketed domain literal, `None` if it isn't valid
+    pub(crate) fn parse(literal: &str) -> Option<Self> {
+        let content = literal.strip_prefix('[')?.strip_suffix(']')?;
+        match content.split_once(':') {
+            Some((tag, address)) if tag.eq_ignore_ascii_case("IPv6") => address
+                .parse::<Ipv6Addr>()
+                .ok()
+                .map(|ip| Self::Ip(ip.into())),
+            Some(_) => GENERAL_LITERAL.captures(content).map(|c| Self::General {
+                tag: c[1].into(),
+                content: c[2].into(),
+            }),
+            None => content
+                .parse::<Ipv4Addr>()
+                .ok()
+                .map(|ip| Self::Ip(ip.into())),
+        }
+    }
+}
+
+impl fmt::Display for AddressLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ip(IpAddr::V4(ip)) => write!(f, "[{}]", ip),
+            Self::Ip(IpAddr::V6(ip)) => write!(f, "[IPv6:{}]", ip),
+            Self::General { tag, content } => write!(f, "[{}:{}]", tag, content),
+        }
+    }
+}
+
+/// The domain, addresses that can't be split are reported whole as
+/// [`Error::InvalidDomain`]
+fn domain_of(contact: &EmailContact) -> Result<&str> {
+    contact
+        .domain()
+        .ok_or_else(|| Error::InvalidDomain(contact.email().unwrap().clone()))
+}
+
+fn to_ascii(domain: &str) -> Result<String> {
+    if domain.starts_with('[') {
+        return Ok(domain.into());
+    }
+    idna::domain_to_ascii(domain).map_err(|_| Error::InvalidDomain(domain.into()))
+}
+
+fn to_unicode(domain: &str) -> Result<String> {
+    if domain.starts_with('[') {
+        return Ok(domain.into());
+    }
+    match idna::domain_to_unicode(domain) {
+        (unicode, Ok(())) => Ok(unicode),
+        (_, Err(_)) => Err(Error::InvalidDomain(domain.into())),
+    }
+}
+
+fn replace_domain(contact: EmailContact, domain: &str) -> EmailContact {
+    let local = match contact.local_part() {
+        Some(local) => local.to_string(),
+        None => return contact,
+    };
+    contact.set_email(format!("{}@{}", local, domain))
+}
+
+fn convert_domains<F>(list: &AddressList, convert: F) -> Result<AddressList>
+where
+    F: Fn(&str) -> Result<String>,
+{
+    let mut converted = list.clone();
+    for contact in converted.as_contacts_mut() {
+        if let Contact::Email(e) = contact {
+            let domain = convert(domain_of(e)?)?;
+            *e = replace_domain(e.clone(), &domain);
+        }
+    }
+    Ok(converted)
+}
+
+impl EmailContact {
+    /// The domain literal, if the address has a valid one instead of a domain
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// # use std::net::{IpAddr, Ipv6Addr};
+    /// let contact = parse_contact("Postmaster <postmaster@[IPv6:2001:db8::1]>").unwrap();
+    /// let contact = match contact {
+    ///     Contact::Email(e) => e,
+    ///     Contact::Garbage(_) => unreachable!(),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     contact.address_literal(),
+    ///     Some(AddressLiteral::Ip(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)))),
+    /// );
+    /// ```
+    pub fn address_literal(&self) -> Option<AddressLiteral> {
+        self.domain()
+            .filter(|_| self.is_domain_literal())
+            .and_then(AddressLiteral::parse)
+    }
+
+    /// The IP address of the domain literal, if there is one
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// let contact = EmailContact::new("postmaster@[192.0.2.1]");
+    ///
+    /// assert_eq!(contact.ip_address(), Some(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1))));
+    /// assert_eq!(EmailContact::new("postmaster@example.org").ip_address(), None);
+    /// ```
+    pub fn ip_address(&self) -> Option<IpAddr> {
+        match self.address_literal()? {
+            AddressLiteral::Ip(ip) => Some(ip),
+            AddressLiteral::General { .. } => None,
+        }
+    }
+
+    /// The domain in its ASCII compatible form (A-labels), as required by
+    /// SMTP servers without `SMTPUTF8` support
+    ///
+    /// Conversion follows UTS #46, domain literals are returned unchanged.
+    /// Addresses without a recognisable domain are reported as
+    /// [`Error::InvalidDomain`] with the whole address.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("kontakt@Bücher.example");
+    /// assert_eq!(contact.domain_ascii().unwrap(), "xn--bcher-kva.example");
+    ///
+    /// let invalid = EmailContact::new("kontakt@xn--invalid-.example");
+    /// assert!(invalid.domain_ascii().is_err());
+    /// ```
+    ///
+    /// [`Error::InvalidDomain`]: error/enum.Error.html#variant.InvalidDomain
+    pub fn domain_ascii(&self) -> Result<String> {
+        to_ascii(domain_of(self)?)
+    }
+
+    /// The domain in its Unicode form (U-labels)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("kontakt@xn--bcher-kva.example");
+    /// assert_eq!(contact.domain_unicode().unwrap(), "bücher.example");
+    /// ```
+    pub fn domain_unicode(&self) -> Result<String> {
+        to_unicode(domain_of(self)?)
+    }
+}
+
+impl AddressList {
+    /// Copy of this list with all domains converted to their ASCII compatible
+    /// form
+    ///
+    /// Garbage is left alone, the first domain that can't be converted (or
+    /// address that can't be split) is reported as [`Error::InvalidDomain`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let list = parse_address_list("Ainm <ríomhphost@bücher.example>").unwrap();
+    ///
+    /// assert!(list.to_ascii_domains().unwrap().deep_eq(&vec![
+    ///     Contact::new("ríomhphost@xn--bcher-kva.example").set_name("Ainm"),
+    /// ].into()));
+    /// ```
+    ///
+    /// [`Error::InvalidDomain`]: error/enum.Error.html#variant.InvalidDomain
+    pub fn to_ascii_domains(&self) -> Result<AddressList> {
+        convert_domains(self, to_ascii)
+    }
+
+    /// Copy of this list with all domains converted to their Unicode form
+    ///
+    /// Garbage is left alone, the first domain that can't be converted (or
+    /// address that can't be split) is reported as [`Error::InvalidDomain`].
+    ///
+    /// [`Error::InvalidDomain`]: error/enum.Error.html#variant.InvalidDomain
+    pub fn to_unicode_domains(&self) -> Result<AddressList> {
+        convert_domains(self, to_unicode)
+    }
+}
@@ -0,0 +1,132 @@
+use crate::address_list::*;
+use crate::parser::is_email;
+use crate::subaddress::Subaddressing;
+
+/// `local@domain`, if that makes a valid address
+fn address(local: &str, domain: &str) -> Option<EmailContact> {
+    let email = format!("{}@{}", local, domain);
+    if local.is_empty() || !is_email(&email) {
+        return None;
+    }
+    Some(EmailContact::new(email))
+}
+
+/// The `HHH=TT=domain=local` part of an SRS0 or SRS1 local part
+fn srs_payload(local: &str) -> Option<&str> {
+    let tag = local.get(..4)?.to_ascii_uppercase();
+    match tag.as_str() {
+        "SRS0" => local[4..].strip_prefix(['=', '+', '-']),
+        // SRS1=HHH=first.forwarder==HHH=TT=domain=local
+        "SRS1" => local.split_once("==").map(|(_, payload)| payload),
+        _ => None,
+    }
+}
+
+impl EmailContact {
+    /// The recipient encoded in a VERP (variable envelope return path)
+    /// address like `bounces+alice=example.org@lists.example.com`
+    ///
+    /// Shorthand for [`decode_verp_with`] using `+` as separator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("bounces+alice=example.org@lists.example.com");
+    ///
+    /// assert_eq!(contact.decode_verp().unwrap().email().unwrap(), "alice@example.org");
+    /// assert!(EmailContact::new("alice+news@example.org").decode_verp().is_none());
+    /// ```
+    ///
+    /// [`decode_verp_with`]: #method.decode_verp_with
+    pub fn decode_verp(&self) -> Option<EmailContact> {
+        self.decode_verp_with(&Subaddressing::default())
+    }
+
+    /// The recipient encoded in a VERP address whose local part is split
+    /// according to `rules`
+    ///
+    /// The recipient is the sub-address with its last `=` turned back into
+    /// an `@`. Returns `None` if that doesn't make a valid address.
+    pub fn decode_verp_with(&self, rules: &Subaddressing) -> Option<EmailContact> {
+        let tag = self.subaddress_with(rules)?;
+        let (local, domain) = tag.rsplit_once('=')?;
+        address(local, domain)
+    }
+
+    /// A VERP address for `recipient`, with this contact as the list's
+    /// bounce address
+    ///
+    /// Shorthand for [`encode_verp_with`] using `+` as separator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let base = EmailContact::new("dev-bounces@lists.example.com");
+    /// let verp = base.encode_verp(&Contact::new("alice@example.org").set_name("Alice")).unwrap();
+    ///
+    /// assert_eq!(verp.email().unwrap(), "dev-bounces+alice=example.org@lists.example.com");
+    /// assert!(base.encode_verp(&GarbageContact::new("alice").to_contact()).is_none());
+    /// ```
+    ///
+    /// [`encode_verp_with`]: #method.encode_verp_with
+    pub fn encode_verp(&self, recipient: &Contact) -> Option<EmailContact> {
+        self.encode_verp_with(recipient, &Subaddressing::default())
+    }
+
+    /// A VERP address for `recipient`, with the separator from `rules`
+    ///
+    /// Any sub-address this contact already has is replaced. Names and
+    /// comments don't carry over, the result is meant for the envelope.
+    /// Returns `None` for garbage, and if either this contact or the
+    /// recipient has a quoted local part, which can't be encoded.
+    pub fn encode_verp_with(
+        &self,
+        recipient: &Contact,
+        rules: &Subaddressing,
+    ) -> Option<EmailContact> {
+        let recipient = match recipient {
+            Contact::Email(e) => e,
+            Contact::Garbage(_) => return None,
+        };
+        let (local, domain) = (recipient.local_part()?, recipient.domain()?);
+        if local.starts_with('"') || self.local_part()?.starts_with('"') {
+            return None;
+        }
+        let base = EmailContact::new(self.email().unwrap());
+        let verp = base.set_subaddress(format!("{}={}", local, domain), rules);
+        is_email(verp.email().unwrap()).then_some(verp)
+    }
+
+    /// The original sender of an SRS (sender rewriting scheme) address like
+    /// `SRS0=HHH=TT=example.org=alice@forwarder.example`
+    ///
+    /// SRS1 addresses, rewritten by a second forwarder, are decoded all the
+    /// way back to the original sender. The hash is not verified, that needs
+    /// the forwarder's secret.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let srs0 = EmailContact::new("SRS0=HHH=TT=example.org=alice@forwarder.example");
+    /// let srs1 = EmailContact::new(
+    ///     "SRS1=JJJ=forwarder.example==HHH=TT=example.org=alice@second.example",
+    /// );
+    ///
+    /// assert_eq!(srs0.decode_srs().unwrap().email().unwrap(), "alice@example.org");
+    /// assert_eq!(srs1.decode_srs().unwrap().email().unwrap(), "alice@example.org");
+    /// assert!(EmailContact::new("alice@example.org").decode_srs().is_none());
+    /// ```
+    pub fn decode_srs(&self) -> Option<EmailContact> {
+        let local = self.local_part_unquoted()?;
+        let mut fields = srs_payload(&local)?.splitn(4, '=');
+        let (hash, timestamp) = (fields.next()?, fields.next()?);
+        let (domain, local) = (fields.next()?, fields.next()?);
+        if hash.is_empty() || timestamp.is_empty() {
+            return None;
+        }
+        address(local, domain)
+    }
+}
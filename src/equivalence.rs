@@ -0,0 +1,145 @@
+use crate::address_list::*;
+use crate::provider::BUILTIN;
+
+/// A notion of when two email addresses belong to the same recipient
+///
+/// Implementors turn an address into a key, two addresses are considered the
+/// same if their keys are. Keys are what hash based operations like
+/// [`Contactsish::dedup`] use, so lists with thousands of contacts don't have
+/// to be compared pairwise.
+///
+/// [`Contactsish::dedup`]: trait.Contactsish.html#method.dedup
+pub trait AddressEquivalence {
+    /// Key that is identical for all addresses we consider the same
+    fn key(&self, email: &str) -> String;
+
+    /// Check if two addresses are the same according to this policy
+    fn equivalent(&self, a: &str, b: &str) -> bool {
+        self.key(a) == self.key(b)
+    }
+}
+
+/// Addresses are only the same if they are byte for byte identical
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Exact;
+
+impl AddressEquivalence for Exact {
+    fn key(&self, email: &str) -> String {
+        email.into()
+    }
+}
+
+/// Domains are compared case-insensitively, local parts as they are
+///
+/// This is what RFC 5321 guarantees, local parts are up to the receiving
+/// server to interpret.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CaseInsensitiveDomain;
+
+impl AddressEquivalence for CaseInsensitiveDomain {
+    fn key(&self, email: &str) -> String {
+        match split_key(email) {
+            Some((local, domain)) => format!("{}@{}", local, domain.to_lowercase()),
+            None => email.into(),
+        }
+    }
+}
+
+/// The whole address is compared case-insensitively, ignoring surrounding
+/// whitespace and a trailing dot on the domain
+///
+/// Not guaranteed by any RFC, but it's how practically every mail server out
+/// there behaves.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Canonicalised;
+
+impl AddressEquivalence for Canonicalised {
+    fn key(&self, email: &str) -> String {
+        email.trim().trim_end_matches('.').to_lowercase()
+    }
+}
+
+/// Provider specific folding (using the built-in [`ProviderRules`]) on top
+/// of [`Canonicalised`]
+///
+/// Gmail for instance ignores dots and everything after a `+` in the local
+/// part, and treats `googlemail.com` as an alias of `gmail.com`. Addresses at
+/// providers without a rule are compared like [`Canonicalised`] does. Use
+/// [`ProviderRules`] directly for a custom rule table.
+///
+/// ```rust
+/// # use email_address_list::*;
+/// assert!(ProviderAware.equivalent("Jane.Doe+news@googlemail.com", "janedoe@gmail.com"));
+/// assert!(!ProviderAware.equivalent("jane.doe@example.org", "janedoe@example.org"));
+/// ```
+///
+/// [`ProviderRules`]: struct.ProviderRules.html
+/// [`Canonicalised`]: struct.Canonicalised.html
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProviderAware;
+
+impl AddressEquivalence for ProviderAware {
+    fn key(&self, email: &str) -> String {
+        BUILTIN.canonical_address(&Canonicalised.key(email))
+    }
+}
+
+/// Like [`CaseInsensitiveDomain`], but domains are compared in their ASCII
+/// compatible form (UTS #46), so `bücher.example` and
+/// `xn--bcher-kva.example` are the same
+///
+/// Domains that can't be converted are compared lower-cased.
+///
+/// [`CaseInsensitiveDomain`]: struct.CaseInsensitiveDomain.html
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IdnaNormalised;
+
+impl AddressEquivalence for IdnaNormalised {
+    fn key(&self, email: &str) -> String {
+        match split_key(email) {
+            Some((local, domain)) => {
+                let domain =
+                    idna::domain_to_ascii(domain).unwrap_or_else(|_| domain.to_lowercase());
+                format!("{}@{}", local, domain)
+            }
+            None => email.into(),
+        }
+    }
+}
+
+/// Split an address into local part and domain at its last `@`, for
+/// building keys
+///
+/// Keys are computed on every comparison, so this avoids running the parser.
+/// Domains can't contain `@` or `"`, so the last `@` is the right one unless
+/// it's inside a quoted local part, in which case there is no domain.
+pub(crate) fn split_key(email: &str) -> Option<(&str, &str)> {
+    match email.rsplit_once('@') {
+        Some((local, domain)) if !local.is_empty() && !domain.contains('"') => {
+            Some((local, domain))
+        }
+        _ => None,
+    }
+}
+
+/// Hashable identity of a [`Contact`] under a given equivalence, garbage is
+/// identified by its contents
+///
+/// [`Contact`]: enum.Contact.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ContactKey {
+    Email(String),
+    Garbage(String),
+}
+
+impl ContactKey {
+    pub(crate) fn new<E>(contact: &Contact, equivalence: &E) -> Self
+    where
+        E: AddressEquivalence + ?Sized,
+    {
+        match contact {
+            Contact::Email(e) => ContactKey::Email(equivalence.key(e.email().unwrap())),
+            Contact::Garbage(g) => ContactKey::Garbage(g.comment().unwrap().clone()),
+        }
+    }
+}
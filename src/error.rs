@@ -7,6 +7,14 @@ pub enum Error {
     PestRuleError(Box<pest::error::Error<Rule>>),
     UnexpectedError(String),
     Empty,
+    /// A domain that can't be converted between Unicode and ASCII forms
+    InvalidDomain(String),
+    /// A URI that isn't what it's supposed to be, e.g. not a `mailto:` URI
+    InvalidUri(String),
+    /// A regular expression that doesn't compile
+    InvalidPattern(String),
+    /// A file that couldn't be read
+    Io(std::io::Error),
 }
 
 impl fmt::Display for Error {
@@ -21,6 +29,12 @@ impl From<pest::error::Error<Rule>> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 pub(crate) fn invalid_nesting(rule: &str) -> Error {
@@ -0,0 +1,204 @@
+use lazy_static::*;
+use regex::Regex;
+use std::ops::Range;
+
+use crate::address_list::*;
+use crate::deobfuscate::{address, OBFUSCATED};
+use crate::parser::{is_delimited_email, is_email};
+
+lazy_static! {
+    // Apostrophes are fine in local parts (o'brien@), not in domains
+    static ref CANDIDATE: Regex =
+        Regex::new(r#"[^\s<>()\[\]{},;:"@]+@[^\s<>()\[\]{},;:"'@]+"#).unwrap();
+    // Quotes and brackets around a candidate, ASCII or not
+    static ref LEADING: Regex = Regex::new(r"^[\p{Ps}\p{Pi}\p{Pf}'`]+").unwrap();
+    static ref TRAILING: Regex = Regex::new(r"[\p{Pe}\p{Pi}\p{Pf}'`.!?]+$").unwrap();
+    static ref QUOTED_NAME: Regex = Regex::new(r#""([^"]+)"\s*$"#).unwrap();
+    static ref WORD: Regex = Regex::new(r"(\S+)\s*$").unwrap();
+}
+
+/// Knobs for [`extract_contacts`]
+///
+/// [`extract_contacts`]: fn.extract_contacts.html
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ExtractOptions {
+    /// Look for names like `Jane Doe <jane@example.org>` or
+    /// `Jane Doe (jane@example.org)` right before addresses
+    pub names: bool,
+    /// Also pick up obfuscated addresses like `jane at example dot org`, see
+    /// [`deobfuscate`] for what's recognised
+    ///
+    /// [`deobfuscate`]: fn.deobfuscate.html
+    pub deobfuscate: bool,
+}
+
+impl ExtractOptions {
+    /// Names on, de-obfuscation off
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_names(mut self, names: bool) -> Self {
+        self.names = names;
+        self
+    }
+
+    pub fn set_deobfuscate(mut self, deobfuscate: bool) -> Self {
+        self.deobfuscate = deobfuscate;
+        self
+    }
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            names: true,
+            deobfuscate: false,
+        }
+    }
+}
+
+/// A contact found by [`extract_contacts`]
+///
+/// [`extract_contacts`]: fn.extract_contacts.html
+#[derive(Debug, Clone)]
+pub struct Extracted {
+    pub contact: EmailContact,
+    /// Byte range of everything that makes up the contact, including its name
+    pub span: Range<usize>,
+    /// Byte range of just the address
+    pub address_span: Range<usize>,
+    /// Whether the address was obfuscated in the text
+    pub deobfuscated: bool,
+}
+
+/// Look for a name right before `start`, returns the name and where it starts
+fn name_before(text: &str, start: usize) -> Option<(String, usize)> {
+    let before = text[..start].trim_end();
+    let before = before
+        .strip_suffix('<')
+        .or_else(|| before.strip_suffix('('))?
+        .trim_end();
+    if let Some(c) = QUOTED_NAME.captures(before) {
+        return Some((c[1].trim().into(), c.get(0).unwrap().start()));
+    }
+    let mut words = Vec::new();
+    let mut name_start = before.len();
+    while words.len() < 4 {
+        let word = match WORD.captures(&before[..name_start]) {
+            Some(c) => c.get(1).unwrap(),
+            None => break,
+        };
+        let capitalised = word.as_str().chars().next().is_some_and(char::is_uppercase);
+        if !capitalised
+            || !word
+                .as_str()
+                .chars()
+                .all(|c| c.is_alphabetic() || "-.'".contains(c))
+        {
+            break;
+        }
+        words.insert(0, word.as_str());
+        name_start = word.start();
+    }
+    if words.is_empty() {
+        return None;
+    }
+    Some((words.join(" "), name_start))
+}
+
+/// Find `address` in `text` at `address_span`, together with a name in front
+/// of it if requested
+fn extracted(
+    text: &str,
+    address: &str,
+    address_span: Range<usize>,
+    deobfuscated: bool,
+    options: &ExtractOptions,
+) -> Extracted {
+    let mut contact = EmailContact::new(address).set_deobfuscated(deobfuscated);
+    let mut span = address_span.clone();
+    if options.names {
+        if let Some((name, start)) = name_before(text, address_span.start) {
+            contact = contact.set_name(name);
+            span.start = start;
+            let after = &text[address_span.end..];
+            let closing = after.len() - after.trim_start_matches([')', '>']).len();
+            span.end += closing.min(1);
+        }
+    }
+    Extracted {
+        contact,
+        span,
+        address_span,
+        deobfuscated,
+    }
+}
+
+/// Pick out addresses from free-form text, e.g. something pasted into a
+/// recipient field
+///
+/// Unlike [`parse_address_list`], this doesn't expect a header, but prose
+/// with addresses somewhere in it. Addresses are validated with the same
+/// `email` rule the parser uses, except that apostrophes are allowed in the
+/// local part, and quotes or brackets around them are left out. Results are
+/// ordered by their position in `text`, spans are byte ranges so the hits
+/// can be highlighted for confirmation.
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let text = "please contact Jane Doe (jane@x.org) or bob at y dot org.";
+///
+/// let found = extract_contacts(text, &ExtractOptions::default());
+/// assert_eq!(found.len(), 1);
+/// assert!(found[0].contact.deep_eq(&EmailContact::new("jane@x.org").set_name("Jane Doe")));
+/// assert_eq!(&text[found[0].span.clone()], "Jane Doe (jane@x.org)");
+///
+/// let options = ExtractOptions::new().set_deobfuscate(true);
+/// let found = extract_contacts(text, &options);
+/// assert_eq!(found.len(), 2);
+/// assert_eq!(found[1].contact.email().unwrap(), "bob@y.org");
+/// assert_eq!(&text[found[1].span.clone()], "bob at y dot org");
+/// assert!(found[1].deobfuscated);
+/// ```
+///
+/// [`parse_address_list`]: fn.parse_address_list.html
+pub fn extract_contacts(text: &str, options: &ExtractOptions) -> Vec<Extracted> {
+    let mut found: Vec<Extracted> = Vec::new();
+    for candidate in CANDIDATE.find_iter(text) {
+        let address = candidate.as_str();
+        let start = LEADING.find(address).map_or(0, |m| m.end());
+        let address = &address[start..];
+        let address = &address[..TRAILING.find(address).map_or(address.len(), |m| m.start())];
+        if is_delimited_email(address) {
+            let start = candidate.start() + start;
+            found.push(extracted(
+                text,
+                address,
+                start..start + address.len(),
+                false,
+                options,
+            ));
+        }
+    }
+    if options.deobfuscate {
+        for c in OBFUSCATED.captures_iter(text) {
+            let span = c.get(0).unwrap().range();
+            if found
+                .iter()
+                .any(|f| f.address_span.start < span.end && span.start < f.address_span.end)
+            {
+                continue;
+            }
+            let address = address(&c);
+            if is_email(&address) {
+                found.push(extracted(text, &address, span, true, options));
+            }
+        }
+        found.sort_by_key(|f| f.span.start);
+    }
+    found
+}
@@ -0,0 +1,153 @@
+use unicode_security::{skeleton, MixedScript};
+
+use crate::address_list::*;
+
+/// Which part of an [`EmailContact`] a [`Homograph`] was found in
+///
+/// [`EmailContact`]: struct.EmailContact.html
+/// [`Homograph`]: enum.Homograph.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContactPart {
+    LocalPart,
+    Domain,
+    Name,
+}
+
+/// Something about a contact that could be used to imitate another one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Homograph {
+    /// Characters of more than one script (say Latin and Cyrillic) in the
+    /// local part, a single domain label or a single word of the name
+    MixedScript(ContactPart),
+    /// Non-ASCII letters or digits whose skeleton (UTS #39) is plain ASCII,
+    /// i.e. text that looks like something that could have been spelled in
+    /// ASCII
+    Confusable { part: ContactPart, skeleton: String },
+}
+
+fn skeleton_of(s: &str) -> String {
+    skeleton(s).collect()
+}
+
+fn check<'a, I>(part: ContactPart, text: &str, pieces: I, found: &mut Vec<Homograph>)
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    if text.is_ascii() {
+        return;
+    }
+    if pieces.into_iter().any(|p| !p.is_single_script()) {
+        found.push(Homograph::MixedScript(part));
+    }
+    // Typographic quotes, dashes and spaces are fine, only letters and
+    // digits that pass for ASCII ones count
+    let letters: String = text.chars().filter(|c| c.is_alphanumeric()).collect();
+    if letters.is_ascii() || !skeleton_of(&letters).is_ascii() {
+        return;
+    }
+    found.push(Homograph::Confusable {
+        part,
+        skeleton: skeleton_of(text),
+    });
+}
+
+impl EmailContact {
+    /// The domain with IDNA labels decoded, so `xn--` spoofs are looked at
+    /// in the form readers see them
+    fn domain_for_display(&self) -> Option<String> {
+        let domain = self.domain()?;
+        Some(
+            self.domain_unicode()
+                .unwrap_or_else(|_| domain.to_lowercase()),
+        )
+    }
+
+    /// Look for mixed scripts and confusable characters in the address and
+    /// name
+    ///
+    /// Domains are checked in their Unicode form, one label at a time, since
+    /// different labels using different scripts is perfectly normal. Names
+    /// are checked word by word for the same reason. Plain ASCII never
+    /// produces a finding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("billing@p\u{0430}ypal.org");
+    ///
+    /// assert_eq!(contact.homographs(), vec![
+    ///     Homograph::MixedScript(ContactPart::Domain),
+    ///     Homograph::Confusable {
+    ///         part: ContactPart::Domain,
+    ///         skeleton: "paypal.org".into(),
+    ///     },
+    /// ]);
+    /// assert!(EmailContact::new("info@bücher.example").homographs().is_empty());
+    /// ```
+    pub fn homographs(&self) -> Vec<Homograph> {
+        let mut found = Vec::new();
+        if let Some(local) = self.local_part_unquoted() {
+            check(
+                ContactPart::LocalPart,
+                &local,
+                Some(local.as_str()),
+                &mut found,
+            );
+        }
+        if let Some(domain) = self.domain_for_display() {
+            check(ContactPart::Domain, &domain, domain.split('.'), &mut found);
+        }
+        if let Some(name) = self.name() {
+            check(ContactPart::Name, name, name.split_whitespace(), &mut found);
+        }
+        found
+    }
+
+    /// The UTS #39 skeleton of the address, with the domain lower-cased and
+    /// IDNA-decoded first
+    ///
+    /// Two addresses with the same skeleton are indistinguishable to most
+    /// readers. Skeletons are only meant for comparing, they aren't
+    /// necessarily readable themselves (`m` becomes `rn`, for instance).
+    /// Returns `None` if the address can't be split into local part and
+    /// domain.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let spoof = EmailContact::new("\u{0430}dmin@xn--pypal-4ve.com");
+    /// let real = EmailContact::new("admin@paypal.com");
+    ///
+    /// assert_eq!(spoof.skeleton(), real.skeleton());
+    /// assert_eq!(real.skeleton().unwrap(), "adrnin@paypal.corn");
+    /// ```
+    pub fn skeleton(&self) -> Option<String> {
+        let local = self.local_part_unquoted()?;
+        let domain = self.domain_for_display()?;
+        Some(format!("{}@{}", skeleton_of(&local), skeleton_of(&domain)))
+    }
+
+    /// Whether `other` is a different address that looks the same as this
+    /// one
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let known = EmailContact::new("ceo@example.com");
+    ///
+    /// assert!(EmailContact::new("ceo@ex\u{0430}mple.com").is_confusable_with(&known));
+    /// assert!(!EmailContact::new("ceo@EXAMPLE.com").is_confusable_with(&known));
+    /// ```
+    pub fn is_confusable_with(&self, other: &EmailContact) -> bool {
+        if self == other {
+            return false;
+        }
+        match (self.skeleton(), other.skeleton()) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+}
@@ -30,5 +30,62 @@ pub mod error;
 mod address_list;
 pub use crate::address_list::*;
 
+mod classify;
+pub use crate::classify::{AddressCategory, ClassificationRule, ClassificationRules};
+
+mod deobfuscate;
+pub use crate::deobfuscate::deobfuscate;
+
+mod domain;
+pub use crate::domain::AddressLiteral;
+
+mod envelope;
+
+mod equivalence;
+pub use crate::equivalence::{
+    AddressEquivalence, Canonicalised, CaseInsensitiveDomain, Exact, IdnaNormalised, ProviderAware,
+};
+
+mod extract;
+pub use crate::extract::{extract_contacts, ExtractOptions, Extracted};
+
+mod homograph;
+pub use crate::homograph::{ContactPart, Homograph};
+
+mod mailto;
+pub use crate::mailto::{parse_mailto, Mailto};
+
+mod name;
+pub use crate::name::NameParts;
+
+mod normalise;
+pub use crate::normalise::NormalisationForm;
+
 mod parser;
-pub use crate::parser::{parse_address_list, parse_contact};
+pub use crate::parser::{parse_address_list, parse_address_list_with, parse_contact, ParseOptions};
+
+mod parts;
+
+mod provider;
+pub use crate::provider::{canonicalize, ProviderRule, ProviderRules};
+
+mod psl;
+pub use crate::psl::PublicSuffixList;
+
+mod reply;
+pub use crate::reply::*;
+
+mod sanitise;
+pub use crate::sanitise::{is_invisible, Field, InvisibleChar, Sanitisation};
+
+mod spoofing;
+pub use crate::spoofing::{ProtectedName, Spoofing, SpoofingOptions};
+
+mod subaddress;
+pub use crate::subaddress::Subaddressing;
+
+mod validation;
+pub use crate::validation::{ValidationLevel, Violation};
+
+mod via;
+pub use crate::via::ListRewrite;
@@ -0,0 +1,176 @@
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::fmt;
+
+use crate::address_list::*;
+use crate::equivalence::Exact;
+use crate::error::*;
+use crate::parser::parse_address_list;
+
+/// Everything but unreserved characters and RFC 6068's `some-delims` without
+/// the `,` that separates addresses
+const ADDRESS: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b'!')
+    .remove(b'$')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b'+')
+    .remove(b';')
+    .remove(b':')
+    .remove(b'@');
+
+/// Header field names and values may contain `,`, just not `&` and `=`
+const HFIELD: &AsciiSet = &ADDRESS.remove(b',');
+
+/// The contents of a `mailto:` URI (RFC 6068)
+///
+/// Recipients from the `to` hfield are merged into [`to`], all header fields
+/// other than `to`, `cc` and `bcc` (e.g. `subject` and `body`) end up in
+/// [`hfields`], decoded and in their original order.
+///
+/// [`to`]: #structfield.to
+/// [`hfields`]: #structfield.hfields
+#[derive(Debug, Clone)]
+pub struct Mailto {
+    pub to: AddressList,
+    pub cc: AddressList,
+    pub bcc: AddressList,
+    pub hfields: Vec<(String, String)>,
+}
+
+impl Default for Mailto {
+    fn default() -> Self {
+        Self {
+            to: Contacts::new().into(),
+            cc: Contacts::new().into(),
+            bcc: Contacts::new().into(),
+            hfields: Vec::new(),
+        }
+    }
+}
+
+fn decode(input: &str) -> Result<String> {
+    percent_decode_str(input)
+        .decode_utf8()
+        .map(|d| d.into_owned())
+        .map_err(|_| Error::InvalidUri(format!("{} is not valid UTF-8", input)))
+}
+
+fn add_recipients(list: &mut AddressList, recipients: &str) -> Result<()> {
+    match parse_address_list(&decode(recipients)?) {
+        Ok(parsed) => list.union(&parsed, &Exact),
+        Err(Error::Empty) => {}
+        Err(e) => return Err(e),
+    }
+    Ok(())
+}
+
+/// Get the recipients and other header fields from a `mailto:` URI
+///
+/// The URI is percent-decoded (`+` is not a space in `mailto:` URIs), the
+/// recipients are parsed with [`parse_address_list`], so they may be just as
+/// messy as anything else.
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let mailto = parse_mailto("mailto:a@x.org,b@y.org?cc=C%20Person%20%3Cc@z.org%3E&subject=Hi%20there").unwrap();
+///
+/// assert!(mailto.to.deep_eq(&vec![
+///     Contact::new("a@x.org"),
+///     Contact::new("b@y.org"),
+/// ].into()));
+/// assert!(mailto.cc.deep_eq(&vec![
+///     Contact::new("c@z.org").set_name("C Person"),
+/// ].into()));
+/// assert_eq!(mailto.hfields, vec![("subject".to_string(), "Hi there".to_string())]);
+/// ```
+///
+/// [`parse_address_list`]: fn.parse_address_list.html
+pub fn parse_mailto<T>(uri: &T) -> Result<Mailto>
+where
+    T: AsRef<str>,
+    T: ?Sized,
+{
+    let uri = uri.as_ref().trim();
+    let rest = match uri.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("mailto:") => &uri[7..],
+        _ => return Err(Error::InvalidUri(format!("{} is not a mailto: URI", uri))),
+    };
+    let (to, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    let mut mailto = Mailto::default();
+    add_recipients(&mut mailto.to, to)?;
+    for hfield in query.split('&').filter(|h| !h.is_empty()) {
+        let (name, value) = hfield.split_once('=').unwrap_or((hfield, ""));
+        let name = decode(name)?;
+        match name.to_lowercase().as_str() {
+            "to" => add_recipients(&mut mailto.to, value)?,
+            "cc" => add_recipients(&mut mailto.cc, value)?,
+            "bcc" => add_recipients(&mut mailto.bcc, value)?,
+            _ => mailto.hfields.push((name, decode(value)?)),
+        }
+    }
+    Ok(mailto)
+}
+
+/// Comma separated, escaped addresses of all non-garbage contacts
+fn encode_addresses(list: &AddressList, set: &'static AsciiSet) -> String {
+    list.contacts()
+        .iter()
+        .filter_map(|c| c.email())
+        .map(|e| utf8_percent_encode(e, set).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// A `mailto:` URI with all recipients as bare addresses, display names,
+/// comments, groups and garbage are left out
+impl fmt::Display for Mailto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mailto:{}", encode_addresses(&self.to, ADDRESS))?;
+        let recipients = [("cc", &self.cc), ("bcc", &self.bcc)]
+            .into_iter()
+            .map(|(name, list)| (name.to_string(), encode_addresses(list, HFIELD)))
+            .filter(|(_, addresses)| !addresses.is_empty());
+        let hfields = self.hfields.iter().map(|(name, value)| {
+            (
+                utf8_percent_encode(name, HFIELD).to_string(),
+                utf8_percent_encode(value, HFIELD).to_string(),
+            )
+        });
+        for (i, (name, value)) in recipients.chain(hfields).enumerate() {
+            write!(f, "{}{}={}", if i == 0 { '?' } else { '&' }, name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl AddressList {
+    /// A `mailto:` URI addressed to all contacts in this list
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let list = parse_address_list(r#"Ainm <ríomhphost@example.org>, "a,b"@example.org"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     list.to_mailto(),
+    ///     "mailto:r%C3%ADomhphost@example.org,%22a%2Cb%22@example.org",
+    /// );
+    /// ```
+    pub fn to_mailto(&self) -> String {
+        Mailto {
+            to: self.clone(),
+            ..Default::default()
+        }
+        .to_string()
+    }
+}
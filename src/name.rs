@@ -0,0 +1,370 @@
+use crate::address_list::*;
+use crate::classify::ROLE_ACCOUNTS;
+
+const PREFIXES: &[&str] = &[
+    "mr", "mrs", "ms", "miss", "mx", "dr", "prof", "sir", "dame", "rev", "fr", "hon",
+];
+const SUFFIXES: &[&str] = &[
+    "jr", "sr", "ii", "iii", "iv", "phd", "md", "esq", "mba", "jd", "dds",
+];
+/// Lower-case words that belong to the family name that follows them
+const PARTICLES: &[&str] = &[
+    "van", "von", "der", "den", "de", "da", "di", "del", "della", "du", "la", "le", "dos", "das",
+    "bin", "ibn", "al", "ter", "ten", "zu",
+];
+
+/// A display name split into its parts by [`EmailContact::name_parts`]
+///
+/// [`EmailContact::name_parts`]: struct.EmailContact.html#method.name_parts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameParts {
+    /// Titles like `Dr.` or `Prof.`
+    pub prefix: Option<String>,
+    /// Given name, including middle names and initials
+    pub given: Option<String>,
+    /// Family name, including particles like `van der`
+    pub family: Option<String>,
+    /// Generational or academic suffixes like `Jr.` or `PhD`
+    pub suffix: Option<String>,
+    /// The name was written family name first with a comma, `Doe, Jane`
+    pub comma_inverted: bool,
+    /// The name is written in Chinese, Japanese or Korean script, where the
+    /// family name naturally comes first
+    pub ideographic: bool,
+}
+
+fn is_listed(word: &str, list: &[&str]) -> bool {
+    let word = word.replace('.', "").to_lowercase();
+    list.contains(&word.as_str())
+}
+
+fn is_ideographic(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'
+        | '\u{3005}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{3130}'..='\u{318F}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}'
+    )
+}
+
+fn join(words: &[&str]) -> Option<String> {
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+/// Move leading words found in `list` out of `words`
+fn take_leading<'a>(words: &mut Vec<&'a str>, list: &[&str]) -> Vec<&'a str> {
+    let count = words
+        .iter()
+        .take(words.len().saturating_sub(1))
+        .take_while(|w| is_listed(w, list))
+        .count();
+    words.drain(..count).collect()
+}
+
+/// Move trailing words found in `list` out of `words`
+fn take_trailing<'a>(words: &mut Vec<&'a str>, list: &[&str]) -> Vec<&'a str> {
+    let count = words
+        .iter()
+        .rev()
+        .take(words.len().saturating_sub(1))
+        .take_while(|w| is_listed(w, list))
+        .count();
+    words.split_off(words.len() - count)
+}
+
+fn ideographic_parts(name: &str) -> NameParts {
+    let words: Vec<_> = name.split_whitespace().collect();
+    let (family, given) = if words.len() > 1 {
+        (words[0].to_string(), join(&words[1..]))
+    } else {
+        // Without a space we have to guess: most family names are a single
+        // character, longer names are more likely to have two
+        let chars: Vec<_> = name.chars().collect();
+        let split = match chars.len() {
+            0 | 1 => chars.len(),
+            2 | 3 => 1,
+            _ => 2,
+        };
+        let given: String = chars[split..].iter().collect();
+        (
+            chars[..split].iter().collect(),
+            Some(given).filter(|g| !g.is_empty()),
+        )
+    };
+    NameParts {
+        family: Some(family),
+        given,
+        ideographic: true,
+        ..Default::default()
+    }
+}
+
+/// Whether a word looks like it could be part of a name rather than a
+/// random token: letters only, and nothing unpronounceable
+fn is_name_like(word: &str) -> bool {
+    if word.is_empty() || !word.chars().all(char::is_alphabetic) || word.chars().count() > 20 {
+        return false;
+    }
+    if !word.is_ascii() {
+        return true;
+    }
+    let mut consonants = 0;
+    let mut has_vowel = false;
+    for c in word.to_ascii_lowercase().chars() {
+        if "aeiouy".contains(c) {
+            has_vowel = true;
+            consonants = 0;
+        } else {
+            consonants += 1;
+            if consonants > 4 {
+                return false;
+            }
+        }
+    }
+    has_vowel || word.len() < 4
+}
+
+fn capitalise(word: &str) -> String {
+    let lower_rest = word.chars().all(char::is_lowercase) || word.chars().all(char::is_uppercase);
+    let mut chars = word.chars();
+    let first = chars.next().map(|c| c.to_uppercase().collect::<String>());
+    let rest: String = if lower_rest {
+        chars.flat_map(char::to_lowercase).collect()
+    } else {
+        chars.collect()
+    };
+    first.unwrap_or_default() + &rest
+}
+
+fn infer_name(local: &str) -> Option<String> {
+    let local = local.split('+').next().unwrap_or(local);
+    let mut words: Vec<_> = local
+        .split(['.', '_', '-'])
+        .filter(|w| !w.is_empty())
+        .collect();
+    // jane.doe2 is still Jane Doe
+    if let Some(last) = words.last_mut() {
+        *last = last.trim_end_matches(|c: char| c.is_ascii_digit());
+    }
+    words.retain(|w| !w.is_empty());
+    if words.is_empty() || words.len() > 4 || !words.iter().all(|w| is_name_like(w)) {
+        return None;
+    }
+    if words
+        .iter()
+        .any(|w| ROLE_ACCOUNTS.contains(&w.to_lowercase().as_str()))
+    {
+        return None;
+    }
+    Some(
+        words
+            .into_iter()
+            .map(capitalise)
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+fn parse_name(name: &str) -> Option<NameParts> {
+    let name = name.trim();
+    if name.is_empty() || name.contains('@') {
+        return None;
+    }
+    if name
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(is_ideographic)
+    {
+        return Some(ideographic_parts(name));
+    }
+
+    let mut segments: Vec<Vec<&str>> = name
+        .split(',')
+        .map(|s| s.split_whitespace().collect::<Vec<_>>())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let mut suffixes = Vec::new();
+    while segments.len() > 1
+        && segments
+            .last()
+            .unwrap()
+            .iter()
+            .all(|w| is_listed(w, SUFFIXES))
+    {
+        let mut last = segments.pop().unwrap();
+        last.append(&mut suffixes);
+        suffixes = last;
+    }
+
+    let mut parts = NameParts::default();
+    let (prefixes, given, family) = match segments.len() {
+        1 => {
+            let mut words = segments.pop().unwrap();
+            let prefixes = take_leading(&mut words, PREFIXES);
+            let mut trailing = take_trailing(&mut words, SUFFIXES);
+            trailing.append(&mut suffixes);
+            suffixes = trailing;
+            let family_start = if words.len() > 1 {
+                let mut start = words.len() - 1;
+                while start > 1 && PARTICLES.contains(&words[start - 1]) {
+                    start -= 1;
+                }
+                start
+            } else {
+                // A single word is more likely a given name
+                words.len()
+            };
+            let family = words.split_off(family_start);
+            (prefixes, words, family)
+        }
+        2 => {
+            parts.comma_inverted = true;
+            let mut given = segments.pop().unwrap();
+            let mut family = segments.pop().unwrap();
+            let prefixes = take_leading(&mut given, PREFIXES);
+            let mut trailing = take_trailing(&mut family, SUFFIXES);
+            trailing.append(&mut suffixes);
+            suffixes = trailing;
+            (prefixes, given, family)
+        }
+        _ => return None,
+    };
+    parts.prefix = join(&prefixes);
+    parts.given = join(&given);
+    parts.family = join(&family);
+    parts.suffix = join(&suffixes);
+    Some(parts)
+}
+
+impl EmailContact {
+    /// Split the display name into given and family name, titles and
+    /// suffixes
+    ///
+    /// This is a heuristic for the names people actually put into their
+    /// mail clients, not a full-blown name parser. `Doe, Jane` is recognised
+    /// as family name first, as are names in Chinese, Japanese and Korean
+    /// script. Returns `None` if there is no name, it contains an address, or
+    /// it has more commas than can be made sense of.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("jane@example.org").set_name("van der Berg, Dr. Jane, PhD");
+    ///
+    /// assert_eq!(contact.name_parts(), Some(NameParts {
+    ///     prefix: Some("Dr.".into()),
+    ///     given: Some("Jane".into()),
+    ///     family: Some("van der Berg".into()),
+    ///     suffix: Some("PhD".into()),
+    ///     comma_inverted: true,
+    ///     ideographic: false,
+    /// }));
+    ///
+    /// let contact = EmailContact::new("taro@example.jp").set_name("山田 太郎");
+    /// let parts = contact.name_parts().unwrap();
+    /// assert_eq!(parts.family.unwrap(), "山田");
+    /// assert_eq!(parts.given.unwrap(), "太郎");
+    /// ```
+    pub fn name_parts(&self) -> Option<NameParts> {
+        self.name().and_then(|n| parse_name(n))
+    }
+
+    /// Propose a display name based on the local part, like `Jane Doe` for
+    /// `jane.doe@example.org`
+    ///
+    /// Dots, underscores and hyphens separate words, subaddresses and
+    /// trailing digits are ignored. Addresses that [`classify`] as something
+    /// other than a person's, and local parts that look random rather than
+    /// like a name give `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let infer = |email| EmailContact::new(email).infer_name();
+    ///
+    /// assert_eq!(infer("jane.doe@example.org").unwrap(), "Jane Doe");
+    /// assert_eq!(infer("j_smith+lists@example.org").unwrap(), "J Smith");
+    /// assert_eq!(infer("support@example.org"), None);
+    /// assert_eq!(infer("xk7qz9wb@example.org"), None);
+    /// ```
+    ///
+    /// [`classify`]: #method.classify
+    pub fn infer_name(&self) -> Option<String> {
+        if !self.classify().is_empty() {
+            return None;
+        }
+        self.local_part_unquoted().and_then(|l| infer_name(&l))
+    }
+
+    /// Set the name to [`infer_name`] if there is no name yet, and mark it as
+    /// [`is_name_inferred`]
+    ///
+    /// [`infer_name`]: #method.infer_name
+    /// [`is_name_inferred`]: #method.is_name_inferred
+    pub fn with_inferred_name(mut self) -> Self {
+        if self.name().is_none() {
+            if let Some(name) = self.infer_name() {
+                self = self.set_name(name);
+                self.name_inferred = self.name().is_some();
+            }
+        }
+        self
+    }
+
+    /// Whether the name was guessed by [`with_inferred_name`] rather than
+    /// parsed or set
+    ///
+    /// Setting a name explicitly resets this. Like [`is_deobfuscated`],
+    /// it's ignored by [`DeepEq`].
+    ///
+    /// [`with_inferred_name`]: #method.with_inferred_name
+    /// [`is_deobfuscated`]: #method.is_deobfuscated
+    /// [`DeepEq`]: trait.DeepEq.html
+    pub fn is_name_inferred(&self) -> bool {
+        self.name_inferred
+    }
+
+    /// The display name in the order it would be spoken, i.e. with a comma
+    /// inversion like `Doe, Jane` undone
+    ///
+    /// Names that can't be analysed, and names in scripts where the family
+    /// name naturally comes first, are returned as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("jane@example.org").set_name("Doe, Jane");
+    /// assert_eq!(contact.display_name_natural_order().unwrap(), "Jane Doe");
+    ///
+    /// let contact = EmailContact::new("jane@example.org").set_name("Doe Jr., Jane");
+    /// assert_eq!(contact.display_name_natural_order().unwrap(), "Jane Doe Jr.");
+    ///
+    /// let contact = EmailContact::new("jane@example.org").set_name("Jane Doe");
+    /// assert_eq!(contact.display_name_natural_order().unwrap(), "Jane Doe");
+    /// ```
+    pub fn display_name_natural_order(&self) -> Option<String> {
+        let name = self.name()?;
+        match self.name_parts() {
+            Some(parts) if parts.comma_inverted => {
+                let words: Vec<_> = [parts.prefix, parts.given, parts.family, parts.suffix]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                Some(words.join(" "))
+            }
+            _ => Some(name.clone()),
+        }
+    }
+}
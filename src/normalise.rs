@@ -0,0 +1,114 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::address_list::*;
+use crate::equivalence::{split_key, AddressEquivalence, CaseInsensitiveDomain};
+
+/// A Unicode normalisation form, see [UAX #15]
+///
+/// NFC only unifies different encodings of the same characters (`Ö` vs. `O`
+/// followed by a combining diaeresis), so it's safe for text that is shown or
+/// sent. NFKC additionally folds compatibility characters like `ﬁ` or
+/// full-width letters, which changes how text looks, so it's better suited
+/// for comparison keys.
+///
+/// As an [`AddressEquivalence`], addresses are normalised before being
+/// compared like [`CaseInsensitiveDomain`] does.
+///
+/// ```rust
+/// # use email_address_list::*;
+/// assert!(NormalisationForm::Nfc.equivalent("jörg@example.org", "jo\u{308}rg@example.org"));
+/// assert!(!NormalisationForm::Nfc.equivalent("ｊｏｅ@example.org", "joe@example.org"));
+/// assert!(NormalisationForm::Nfkc.equivalent("ｊｏｅ@example.org", "joe@example.org"));
+/// ```
+///
+/// [UAX #15]: https://www.unicode.org/reports/tr15/
+/// [`AddressEquivalence`]: trait.AddressEquivalence.html
+/// [`CaseInsensitiveDomain`]: struct.CaseInsensitiveDomain.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NormalisationForm {
+    Nfc,
+    Nfkc,
+}
+
+impl NormalisationForm {
+    pub fn normalise(&self, text: &str) -> String {
+        match self {
+            NormalisationForm::Nfc => text.nfc().collect(),
+            NormalisationForm::Nfkc => text.nfkc().collect(),
+        }
+    }
+
+    /// Normalise local part and domain separately, so NFKC can't turn
+    /// something into an `@` that changes where the address is split
+    fn normalise_address(&self, email: &str) -> String {
+        match split_key(email) {
+            Some((local, domain)) => {
+                format!("{}@{}", self.normalise(local), self.normalise(domain))
+            }
+            None => self.normalise(email),
+        }
+    }
+}
+
+impl AddressEquivalence for NormalisationForm {
+    fn key(&self, email: &str) -> String {
+        CaseInsensitiveDomain.key(&self.normalise_address(email))
+    }
+}
+
+impl EmailContact {
+    /// The form this contact was normalised to, if any
+    ///
+    /// Like [`is_deobfuscated`], this is ignored by [`DeepEq`].
+    ///
+    /// [`is_deobfuscated`]: #method.is_deobfuscated
+    /// [`DeepEq`]: trait.DeepEq.html
+    pub fn normalisation(&self) -> Option<NormalisationForm> {
+        self.normalisation
+    }
+
+    /// Normalise name, comment and address to `form`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("jo\u{308}rg@example.org")
+    ///     .set_name("Jo\u{308}rg")
+    ///     .normalise(NormalisationForm::Nfc);
+    ///
+    /// assert_eq!(contact.name().unwrap(), "Jörg");
+    /// assert_eq!(contact.email().unwrap(), "jörg@example.org");
+    /// assert_eq!(contact.normalisation(), Some(NormalisationForm::Nfc));
+    /// ```
+    pub fn normalise(mut self, form: NormalisationForm) -> Self {
+        self.name = self.name.map(|n| form.normalise(&n));
+        self.comment = self.comment.map(|c| form.normalise(&c));
+        self.email = form.normalise_address(&self.email);
+        self.normalisation = Some(form);
+        self
+    }
+}
+
+impl AddressList {
+    /// Normalise the group name and all contacts to `form`, garbage included
+    ///
+    /// See [`EmailContact::normalise`]. Parsing can do this right away, see
+    /// [`ParseOptions`].
+    ///
+    /// [`EmailContact::normalise`]: struct.EmailContact.html#method.normalise
+    /// [`ParseOptions`]: struct.ParseOptions.html
+    pub fn normalise(&mut self, form: NormalisationForm) {
+        if let AddressList::Group(g) = self {
+            g.name = form.normalise(&g.name);
+        }
+        for contact in self.as_contacts_mut() {
+            *contact = match contact {
+                Contact::Email(e) => e.clone().normalise(form).into(),
+                Contact::Garbage(g) => {
+                    GarbageContact::new(form.normalise(g.comment().unwrap())).into()
+                }
+            };
+        }
+    }
+}
@@ -10,13 +10,15 @@ use crate::error::*;
 use std::convert::AsRef;
 
 use crate::address_list::*;
+use crate::deobfuscate::deobfuscate_tracked;
+use crate::normalise::NormalisationForm;
 
 lazy_static! {
     static ref CSV: Regex = Regex::new(
         r#"[^",]*"[^"\\]*\\.[^"\\]+"[^,"]+@[^,"]+|[^",]*".*?"[^,"]*@[^,"]*|[^,"]+@[^,"]+"#,
     )
     .unwrap();
-    static ref SSV: Regex = Regex::new(r#"[^;"]?".*?"[^;"]*|[^;"]*"#).unwrap();
+    static ref SSV: Regex = Regex::new(r#"[^;"]*".*?"[^;"]*|[^;"]*"#).unwrap();
 }
 
 #[derive(Parser)]
@@ -33,12 +35,23 @@ fn parse_contact_pair(pair: Pair<'_, Rule>) -> Option<Result<Contact>> {
                 None => return Some(Err(invalid_empty("name"))),
             },
             Rule::email | Rule::mailbox => c = c.set_email(inner.as_str()),
-            Rule::email_angle | Rule::mailbox_angle => match inner.into_inner().next() {
-                Some(s) => c = c.set_email(s.as_str()),
-                None => {
-                    return Some(Err(invalid_empty("email_angle or mailbox_angle")));
+            Rule::email_angle | Rule::mailbox_angle => {
+                let mut email = None;
+                for part in inner.into_inner() {
+                    match part.as_rule() {
+                        Rule::route => {
+                            c = c.set_route(part.into_inner().map(|hop| hop.as_str()));
+                        }
+                        _ => email = Some(part.as_str()),
+                    }
                 }
-            },
+                match email {
+                    Some(s) => c = c.set_email(s),
+                    None => {
+                        return Some(Err(invalid_empty("email_angle or mailbox_angle")));
+                    }
+                }
+            }
             Rule::comment => c = c.set_comment(inner.as_str()),
             Rule::garbage => {
                 let garbage = inner.as_str();
@@ -101,6 +114,49 @@ fn parse_pairs(pairs: Pairs<'_, Rule>) -> Result<AddressList> {
     Ok(AddressList::from(contacts))
 }
 
+/// Split an address into local part and domain according to the grammar's
+/// `local` and `domain` rules
+///
+/// Quoted local parts may contain `@`, so simply splitting doesn't cut it.
+pub(crate) fn split_address(email: &str) -> Option<(&str, &str)> {
+    let mut inner = Parser::parse(Rule::addr_spec, email)
+        .ok()?
+        .next()?
+        .into_inner();
+    match (inner.next(), inner.next()) {
+        (Some(local), Some(domain)) => Some((local.as_str(), domain.as_str())),
+        _ => None,
+    }
+}
+
+/// Check if all of `candidate` matches the grammar's `email` rule
+pub(crate) fn is_email(candidate: &str) -> bool {
+    match Parser::parse(Rule::email, candidate) {
+        Ok(mut pairs) => pairs.next().map(|p| p.as_str().len()) == Some(candidate.len()),
+        Err(_) => false,
+    }
+}
+
+/// Like [`is_email`], for a candidate that has already been picked out of
+/// its surroundings, so apostrophes may appear in the local part
+pub(crate) fn is_delimited_email(candidate: &str) -> bool {
+    Parser::parse(Rule::delimited_email, candidate).is_ok()
+}
+
+/// Whether the end of `text` is inside a quoted string
+fn in_quotes(text: &str) -> bool {
+    let (mut quoted, mut escaped) = (false, false);
+    for c in text.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            _ => {}
+        }
+    }
+    quoted
+}
+
 fn check_empty<T>(address_list: &T) -> Result<&str>
 where
     T: AsRef<str>,
@@ -284,6 +340,105 @@ where
     Ok(output)
 }
 
+/// Knobs for [`parse_address_list_with`]
+///
+/// [`parse_address_list_with`]: fn.parse_address_list_with.html
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Run the input through [`deobfuscate`] first, contacts whose addresses
+    /// were changed by that are marked as [`is_deobfuscated`]
+    ///
+    /// [`deobfuscate`]: fn.deobfuscate.html
+    /// [`is_deobfuscated`]: struct.EmailContact.html#method.is_deobfuscated
+    pub deobfuscate: bool,
+    /// Normalise the result to this form, see [`AddressList::normalise`]
+    ///
+    /// [`AddressList::normalise`]: enum.AddressList.html#method.normalise
+    pub normalisation: Option<NormalisationForm>,
+}
+
+impl ParseOptions {
+    /// No extra processing, same as [`parse_address_list`]
+    ///
+    /// [`parse_address_list`]: fn.parse_address_list.html
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_deobfuscate(mut self, deobfuscate: bool) -> Self {
+        self.deobfuscate = deobfuscate;
+        self
+    }
+
+    pub fn set_normalisation(mut self, form: NormalisationForm) -> Self {
+        self.normalisation = Some(form);
+        self
+    }
+}
+
+/// Like [`parse_address_list`], with some optional extra processing
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let options = ParseOptions::new().set_deobfuscate(true);
+/// let result = parse_address_list_with(
+///     "Ainm <ainm (at) example (dot) org>, eile@example.org",
+///     &options,
+/// ).unwrap();
+///
+/// let contacts: Vec<_> = result.contacts().iter().map(|c| match c {
+///     Contact::Email(e) => e.clone(),
+///     Contact::Garbage(_) => unreachable!(),
+/// }).collect();
+///
+/// assert!(contacts[0].deep_eq(&EmailContact::new("ainm@example.org").set_name("Ainm")));
+/// assert!(contacts[0].is_deobfuscated());
+/// assert!(!contacts[1].is_deobfuscated());
+/// ```
+///
+/// [`parse_address_list`]: fn.parse_address_list.html
+pub fn parse_address_list_with<T>(address_list: &T, options: &ParseOptions) -> Result<AddressList>
+where
+    T: AsRef<str>,
+    T: ?Sized,
+{
+    let input = check_empty(address_list)?;
+    let mut output = if options.deobfuscate {
+        let (deobfuscated, spans) = deobfuscate_tracked(input);
+        let mut output = parse_address_list(&deobfuscated)?;
+        // Contacts come in the order of the text, find each one's address
+        // after the previous one and check whether it was rewritten
+        let mut cursor = 0;
+        for contact in output.as_contacts_mut() {
+            if let Contact::Email(e) = contact {
+                let email = e.email().unwrap().as_str();
+                let found = deobfuscated[cursor..]
+                    .match_indices(email)
+                    .map(|(i, _)| cursor + i)
+                    .find(|&i| {
+                        spans.iter().any(|s| s.start == i) || !in_quotes(&deobfuscated[..i])
+                    });
+                if let Some(start) = found {
+                    cursor = start + email.len();
+                    if spans.contains(&(start..cursor)) {
+                        *e = e.clone().set_deobfuscated(true);
+                    }
+                }
+            }
+        }
+        output
+    } else {
+        parse_address_list(input)?
+    };
+    if let Some(form) = options.normalisation {
+        output.normalise(form);
+    }
+    Ok(output)
+}
+
 /// Parse only a single [`Contact`], ignore the rest
 ///
 /// Just like [`parse_address_list`], this function "fails" with
@@ -0,0 +1,63 @@
+use crate::address_list::*;
+use crate::parser::split_address;
+
+impl EmailContact {
+    fn parts(&self) -> Option<(&str, &str)> {
+        self.email().and_then(|e| split_address(e))
+    }
+
+    /// The local part as it appears in the address, including any quotes
+    ///
+    /// Returns `None` if the address can't be split into local part and
+    /// domain.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new(r#""a@b"@example.org"#);
+    ///
+    /// assert_eq!(contact.local_part(), Some(r#""a@b""#));
+    /// assert_eq!(contact.domain(), Some("example.org"));
+    /// ```
+    pub fn local_part(&self) -> Option<&str> {
+        self.parts().map(|(local, _)| local)
+    }
+
+    /// The local part with quotes and quoted-pair escapes removed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new(r#""John \"Q\" Public"@example.org"#);
+    ///
+    /// assert_eq!(contact.local_part_unquoted().unwrap(), r#"John "Q" Public"#);
+    /// ```
+    pub fn local_part_unquoted(&self) -> Option<String> {
+        let local = self.local_part()?;
+        let mut unquoted = String::with_capacity(local.len());
+        let mut quoted = false;
+        let mut chars = local.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => quoted = !quoted,
+                '\\' if quoted => unquoted.extend(chars.next()),
+                _ => unquoted.push(c),
+            }
+        }
+        Some(unquoted)
+    }
+
+    /// The domain as it appears in the address, including brackets for
+    /// domain literals
+    pub fn domain(&self) -> Option<&str> {
+        self.parts().map(|(_, domain)| domain)
+    }
+
+    /// Check if the domain is an address literal like `[192.0.2.1]` or
+    /// `[IPv6:2001:db8::1]`
+    pub fn is_domain_literal(&self) -> bool {
+        self.domain().is_some_and(|d| d.starts_with('['))
+    }
+}
@@ -0,0 +1,241 @@
+use lazy_static::*;
+use std::collections::HashMap;
+
+use crate::address_list::*;
+use crate::equivalence::{split_key, AddressEquivalence};
+use crate::subaddress::split_unquoted;
+
+lazy_static! {
+    pub(crate) static ref BUILTIN: ProviderRules = ProviderRules::builtin();
+}
+
+/// How a mail provider interprets the addresses it hosts
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let rule = ProviderRule::new("example.org")
+///     .set_aliases(&["example.net"])
+///     .set_subaddress_separator("-")
+///     .set_case_insensitive(true);
+/// let rules = ProviderRules::empty().set_rule(rule);
+///
+/// assert!(rules.equivalent("User-Lists@example.net", "user@example.org"));
+/// assert!(!rules.equivalent("user@example.net", "user@example.com"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProviderRule {
+    domain: String,
+    aliases: Vec<String>,
+    ignore_dots: bool,
+    subaddress_separator: Option<String>,
+    case_insensitive: bool,
+}
+
+impl ProviderRule {
+    /// A rule for `domain`, which is also the canonical domain for all of its
+    /// aliases
+    ///
+    /// By default, dots are significant, there's no sub-addressing, and the
+    /// local part is case-sensitive.
+    pub fn new<T>(domain: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            domain: domain.as_ref().to_lowercase(),
+            aliases: Vec::new(),
+            ignore_dots: false,
+            subaddress_separator: None,
+            case_insensitive: false,
+        }
+    }
+
+    /// Domains that deliver to the same mailboxes as the canonical one
+    pub fn set_aliases<T>(mut self, aliases: &[T]) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.aliases = aliases.iter().map(|a| a.as_ref().to_lowercase()).collect();
+        self
+    }
+
+    /// Whether dots in the local part are ignored
+    pub fn set_ignore_dots(mut self, ignore_dots: bool) -> Self {
+        self.ignore_dots = ignore_dots;
+        self
+    }
+
+    /// The separator after which everything in the local part is ignored
+    pub fn set_subaddress_separator<T>(mut self, separator: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let separator = separator.as_ref();
+        self.subaddress_separator = Some(separator.into()).filter(|s: &String| !s.is_empty());
+        self
+    }
+
+    /// Whether the local part is compared case-insensitively
+    pub fn set_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Quoted parts of the local part keep their separators and dots
+    fn canonical_local(&self, local: &str) -> String {
+        let mut local = if self.case_insensitive {
+            local.to_lowercase()
+        } else {
+            local.to_string()
+        };
+        if let Some(separator) = &self.subaddress_separator {
+            if let Some((base, _)) = split_unquoted(&local, separator) {
+                local = base.into();
+            }
+        }
+        if self.ignore_dots {
+            let mut quoted = false;
+            let mut escaped = false;
+            local.retain(|c| {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' if quoted => escaped = true,
+                    '"' => quoted = !quoted,
+                    '.' if !quoted => return false,
+                    _ => {}
+                }
+                true
+            });
+        }
+        local
+    }
+}
+
+/// A table of [`ProviderRule`]s, keyed by domain
+///
+/// The [`builtin`] table contains rules for a few large providers, which are
+/// deliberately conservative: only behaviour the providers document is
+/// folded. Users can add their own rules, replacing built-in ones for the
+/// same domains. Addresses at domains without a rule only get their domain
+/// lower-cased.
+///
+/// When used as an [`AddressEquivalence`], addresses are compared by their
+/// canonical form.
+///
+/// [`ProviderRule`]: struct.ProviderRule.html
+/// [`builtin`]: #method.builtin
+/// [`AddressEquivalence`]: trait.AddressEquivalence.html
+#[derive(Debug, Clone)]
+pub struct ProviderRules {
+    rules: Vec<ProviderRule>,
+    domains: HashMap<String, usize>,
+}
+
+impl ProviderRules {
+    /// A table without any rules
+    pub fn empty() -> Self {
+        Self {
+            rules: Vec::new(),
+            domains: HashMap::new(),
+        }
+    }
+
+    /// Rules for Gmail, Outlook.com, iCloud, Fastmail and Proton
+    pub fn builtin() -> Self {
+        let plus = |domain: &str| {
+            ProviderRule::new(domain)
+                .set_subaddress_separator("+")
+                .set_case_insensitive(true)
+        };
+        let mut rules = ProviderRules::empty()
+            .set_rule(
+                plus("gmail.com")
+                    .set_aliases(&["googlemail.com"])
+                    .set_ignore_dots(true),
+            )
+            .set_rule(ProviderRule::new("icloud.com").set_aliases(&["me.com", "mac.com"]))
+            .set_rule(plus("proton.me").set_aliases(&["protonmail.com", "protonmail.ch"]))
+            .set_rule(plus("fastmail.com"));
+        for domain in ["outlook.com", "hotmail.com", "live.com", "msn.com"] {
+            rules.add(plus(domain));
+        }
+        rules
+    }
+
+    /// Add a rule, replacing existing rules for any of the same domains
+    ///
+    /// A replaced rule is gone for all of its domains, aliases the new rule
+    /// doesn't mention fall back to having no rule.
+    pub fn add(&mut self, rule: ProviderRule) {
+        let index = self.rules.len();
+        let replaced: Vec<usize> = std::iter::once(&rule.domain)
+            .chain(&rule.aliases)
+            .filter_map(|domain| self.domains.get(domain).copied())
+            .collect();
+        self.domains.retain(|_, i| !replaced.contains(i));
+        for domain in std::iter::once(&rule.domain).chain(&rule.aliases) {
+            self.domains.insert(domain.clone(), index);
+        }
+        self.rules.push(rule);
+    }
+
+    /// Builder style version of [`add`]
+    ///
+    /// [`add`]: #method.add
+    pub fn set_rule(mut self, rule: ProviderRule) -> Self {
+        self.add(rule);
+        self
+    }
+
+    /// The rule for `domain`, if there is one
+    pub fn rule(&self, domain: &str) -> Option<&ProviderRule> {
+        self.domains
+            .get(&domain.to_lowercase())
+            .map(|&i| &self.rules[i])
+    }
+
+    /// The canonical form of `email`, or `email` itself if it can't be split
+    /// into local part and domain
+    pub fn canonical_address(&self, email: &str) -> String {
+        let (local, domain) = match split_key(email) {
+            Some(parts) => parts,
+            None => return email.into(),
+        };
+        match self.rule(domain) {
+            Some(rule) => format!("{}@{}", rule.canonical_local(local), rule.domain),
+            None => format!("{}@{}", local, domain.to_lowercase()),
+        }
+    }
+}
+
+impl AddressEquivalence for ProviderRules {
+    fn key(&self, email: &str) -> String {
+        self.canonical_address(email)
+    }
+}
+
+/// The canonical form of `contact` according to `rules`, name and comment
+/// are kept
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let rules = ProviderRules::builtin();
+/// let contact = EmailContact::new("Jane.Doe+crm@GoogleMail.com").set_name("Jane");
+///
+/// assert!(canonicalize(&contact, &rules)
+///     .deep_eq(&EmailContact::new("janedoe@gmail.com").set_name("Jane")));
+///
+/// let custom = ProviderRules::builtin()
+///     .set_rule(ProviderRule::new("example.org").set_subaddress_separator("-"));
+/// let contact = EmailContact::new("support-billing@EXAMPLE.org");
+///
+/// assert_eq!(canonicalize(&contact, &custom).email().unwrap(), "support@example.org");
+/// ```
+pub fn canonicalize(contact: &EmailContact, rules: &ProviderRules) -> EmailContact {
+    let email = rules.canonical_address(contact.email().unwrap());
+    contact.clone().set_email(email)
+}
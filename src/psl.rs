@@ -0,0 +1,224 @@
+use lazy_static::*;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::address_list::*;
+use crate::error::Result;
+
+lazy_static! {
+    static ref BUILTIN: PublicSuffixList =
+        PublicSuffixList::parse(include_str!("../data/public_suffix_list.dat"));
+}
+
+/// The [Public Suffix List], for telling which part of a domain is up to
+/// its owner
+///
+/// The [`builtin`] list is a copy embedded at build time, to update it
+/// replace `data/public_suffix_list.dat` and rebuild. A fresher list can also
+/// be loaded at runtime with [`from_file`] or [`parse`]. ICANN and private
+/// suffixes are treated the same, so `example.github.io` is registrable.
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let list = PublicSuffixList::parse("uk\nco.uk\n*.ck\n!www.ck");
+///
+/// assert_eq!(list.public_suffix("mail.example.co.uk").unwrap(), "co.uk");
+/// assert_eq!(list.registrable_domain("mail.example.co.uk").unwrap(), "example.co.uk");
+/// assert_eq!(list.public_suffix("a.b.ck").unwrap(), "b.ck");
+/// assert_eq!(list.registrable_domain("www.ck").unwrap(), "www.ck");
+/// assert_eq!(list.registrable_domain("co.uk"), None);
+/// ```
+///
+/// [Public Suffix List]: https://publicsuffix.org/
+/// [`builtin`]: #method.builtin
+/// [`from_file`]: #method.from_file
+/// [`parse`]: #method.parse
+#[derive(Debug, Clone)]
+pub struct PublicSuffixList {
+    rules: HashSet<String>,
+    wildcards: HashSet<String>,
+    exceptions: HashSet<String>,
+}
+
+impl PublicSuffixList {
+    /// Read a list in the format of `public_suffix_list.dat`
+    pub fn parse(list: &str) -> Self {
+        let mut parsed = Self {
+            rules: HashSet::new(),
+            wildcards: HashSet::new(),
+            exceptions: HashSet::new(),
+        };
+        for line in list.lines() {
+            let rule = match line.split_whitespace().next() {
+                Some(rule) if !rule.starts_with("//") => rule.to_lowercase(),
+                _ => continue,
+            };
+            if let Some(exception) = rule.strip_prefix('!') {
+                parsed.exceptions.insert(exception.into());
+            } else if let Some(parent) = rule.strip_prefix("*.") {
+                parsed.wildcards.insert(parent.into());
+            } else {
+                parsed.rules.insert(rule);
+            }
+        }
+        parsed
+    }
+
+    /// The copy embedded at build time, parsed once and shared
+    pub fn builtin() -> &'static Self {
+        &BUILTIN
+    }
+
+    pub fn from_file<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    /// Whether `label` is a top-level domain on the list
+    pub(crate) fn is_tld(&self, label: &str) -> bool {
+        let (label, _) = idna::domain_to_unicode(&label.to_lowercase());
+        self.rules.contains(&label) || self.wildcards.contains(&label)
+    }
+
+    /// How many of the labels at the end of `labels` make up the public
+    /// suffix
+    fn suffix_len(&self, labels: &[&str]) -> usize {
+        for i in 0..labels.len() {
+            let candidate = labels[i..].join(".");
+            if self.exceptions.contains(&candidate) {
+                return labels.len() - i - 1;
+            }
+            if self.rules.contains(&candidate)
+                || (i + 1 < labels.len() && self.wildcards.contains(&labels[i + 1..].join(".")))
+            {
+                return labels.len() - i;
+            }
+        }
+        // The implicit `*` rule
+        1
+    }
+
+    /// Lower-cased labels of `domain`, and the number of them belonging to
+    /// the public suffix
+    fn split(&self, domain: &str) -> Option<(Vec<String>, usize)> {
+        let domain = domain.trim_end_matches('.').to_lowercase();
+        if domain.is_empty() || domain.starts_with('[') {
+            return None;
+        }
+        // The list uses Unicode labels
+        let (unicode, _) = idna::domain_to_unicode(&domain);
+        let unicode_labels: Vec<_> = unicode.split('.').collect();
+        let labels: Vec<String> = domain.split('.').map(String::from).collect();
+        if labels.iter().any(|l| l.is_empty()) || labels.len() != unicode_labels.len() {
+            return None;
+        }
+        let suffix_len = self.suffix_len(&unicode_labels);
+        Some((labels, suffix_len))
+    }
+
+    /// The public suffix of `domain`, lower-cased but otherwise in the form
+    /// it was given
+    pub fn public_suffix(&self, domain: &str) -> Option<String> {
+        let (labels, suffix_len) = self.split(domain)?;
+        Some(labels[labels.len() - suffix_len..].join("."))
+    }
+
+    /// The public suffix of `domain` plus one label, `None` if `domain` is a
+    /// public suffix itself
+    pub fn registrable_domain(&self, domain: &str) -> Option<String> {
+        let (labels, suffix_len) = self.split(domain)?;
+        if labels.len() <= suffix_len {
+            return None;
+        }
+        Some(labels[labels.len() - suffix_len - 1..].join("."))
+    }
+}
+
+impl EmailContact {
+    /// The public suffix of the domain, according to the embedded
+    /// [`PublicSuffixList`]
+    ///
+    /// Returns `None` for address literals and addresses that can't be split.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("jane@mail.dept.Example.CO.UK");
+    ///
+    /// assert_eq!(contact.public_suffix().unwrap(), "co.uk");
+    /// assert_eq!(contact.registrable_domain().unwrap(), "example.co.uk");
+    /// ```
+    ///
+    /// [`PublicSuffixList`]: struct.PublicSuffixList.html
+    pub fn public_suffix(&self) -> Option<String> {
+        self.public_suffix_with(&BUILTIN)
+    }
+
+    pub fn public_suffix_with(&self, list: &PublicSuffixList) -> Option<String> {
+        list.public_suffix(self.domain()?)
+    }
+
+    /// The domain the organisation behind the address registered, according
+    /// to the embedded [`PublicSuffixList`]
+    ///
+    /// [`PublicSuffixList`]: struct.PublicSuffixList.html
+    pub fn registrable_domain(&self) -> Option<String> {
+        self.registrable_domain_with(&BUILTIN)
+    }
+
+    pub fn registrable_domain_with(&self, list: &PublicSuffixList) -> Option<String> {
+        list.registrable_domain(self.domain()?)
+    }
+}
+
+impl AddressList {
+    /// Sort contacts into groups named after their [`registrable_domain`]
+    ///
+    /// Groups are ordered by first appearance. Addresses without a
+    /// registrable domain are grouped by their lower-cased domain, garbage
+    /// is left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let list = parse_address_list(
+    ///     "a@mail.example.co.uk, b@example.org, c@EXAMPLE.co.uk, d@sub.example.org",
+    /// ).unwrap();
+    ///
+    /// let groups = list.group_by_organisation();
+    /// assert_eq!(groups.len(), 2);
+    /// assert_eq!(groups[0].name, "example.co.uk");
+    /// assert_eq!(groups[0].to_string(), r#""example.co.uk": <a@mail.example.co.uk>, <c@EXAMPLE.co.uk>;"#);
+    /// assert_eq!(groups[1].name, "example.org");
+    /// ```
+    ///
+    /// [`registrable_domain`]: struct.EmailContact.html#method.registrable_domain
+    pub fn group_by_organisation(&self) -> Vec<Group> {
+        self.group_by_organisation_with(&BUILTIN)
+    }
+
+    pub fn group_by_organisation_with(&self, list: &PublicSuffixList) -> Vec<Group> {
+        let mut groups: Vec<Group> = Vec::new();
+        for contact in self.contacts() {
+            let e = match contact {
+                Contact::Email(e) => e,
+                Contact::Garbage(_) => continue,
+            };
+            let organisation = match e.registrable_domain_with(list) {
+                Some(domain) => domain,
+                None => e.domain().unwrap_or(e.email().unwrap()).to_lowercase(),
+            };
+            match groups.iter_mut().find(|g| g.name == organisation) {
+                Some(group) => group.add(contact.clone()),
+                None => groups.push(Group::new(organisation).set_contacts(vec![contact.clone()])),
+            }
+        }
+        groups
+    }
+}
@@ -0,0 +1,173 @@
+use crate::address_list::*;
+use crate::equivalence::CaseInsensitiveDomain;
+
+/// Address lists found in the headers of a message that is being replied to
+///
+/// Headers that weren't present (or couldn't be parsed) should be left as
+/// `None`.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderAddresses {
+    pub from: Option<AddressList>,
+    pub reply_to: Option<AddressList>,
+    pub to: Option<AddressList>,
+    pub cc: Option<AddressList>,
+    pub mail_followup_to: Option<AddressList>,
+    pub mail_reply_to: Option<AddressList>,
+}
+
+/// Knobs for [`reply_all`]
+///
+/// [`reply_all`]: fn.reply_all.html
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ReplyOptions {
+    /// Send the reply to `Mail-Followup-To` only, if the header is present
+    pub use_mail_followup_to: bool,
+    /// Prefer `Mail-Reply-To` over `Reply-To` and `From` for the author
+    pub use_mail_reply_to: bool,
+}
+
+impl ReplyOptions {
+    /// Both `Mail-Followup-To` and `Mail-Reply-To` are used
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_use_mail_followup_to(mut self, use_mail_followup_to: bool) -> Self {
+        self.use_mail_followup_to = use_mail_followup_to;
+        self
+    }
+
+    pub fn set_use_mail_reply_to(mut self, use_mail_reply_to: bool) -> Self {
+        self.use_mail_reply_to = use_mail_reply_to;
+        self
+    }
+}
+
+impl Default for ReplyOptions {
+    fn default() -> Self {
+        Self {
+            use_mail_followup_to: true,
+            use_mail_reply_to: true,
+        }
+    }
+}
+
+fn non_empty(list: &Option<AddressList>) -> Option<&AddressList> {
+    list.as_ref().filter(|l| !l.is_empty() || l.is_group())
+}
+
+/// The list, if it has at least one contact that isn't garbage
+fn usable(list: &Option<AddressList>) -> Option<&AddressList> {
+    list.as_ref()
+        .filter(|l| l.contacts().iter().any(|c| !c.is_garbage()))
+}
+
+/// Merge `sources` into one list, leaving out garbage, `me` and everything in
+/// `exclude`
+///
+/// If everything comes from a single group, the group is kept intact.
+fn collect(sources: &[&AddressList], me: &[Contact], exclude: &Contacts) -> AddressList {
+    let mut contacts = Contacts::new();
+    let mut contributors = Vec::new();
+    for source in sources {
+        let mut incoming = source.contacts().clone();
+        incoming.retain(|c| !c.is_garbage() && !me.contains(c));
+        incoming.difference(exclude, &CaseInsensitiveDomain);
+        let before = contacts.len();
+        contacts.merge_preferring_names(&incoming, &CaseInsensitiveDomain);
+        if contacts.len() > before || source.is_group() {
+            contributors.push(source);
+        }
+    }
+    match contributors.as_slice() {
+        [AddressList::Group(g)] => Group::new(&g.name).set_contacts(contacts).into(),
+        _ => contacts.into(),
+    }
+}
+
+/// Work out the `To` and `Cc` recipients of a reply to all participants
+///
+/// The author (`Mail-Reply-To`, `Reply-To` or `From`, in that order) becomes
+/// the `To` recipient, everybody from the original `To` and `Cc` is carried
+/// over into `Cc`. If a `Mail-Followup-To` header is present, its recipients
+/// are used for `To` instead and `Cc` stays empty. Whatever is given as `me`
+/// is removed from both lists, so is garbage.
+///
+/// Contacts are de-duplicated by address, keeping the first occurrence unless
+/// a later one has a display name and the first one doesn't. Should all
+/// recipients of a list come from a single group, the group is preserved.
+///
+/// If we are the author of the original message, the reply goes to the
+/// original `To` recipients instead, with the original `Cc` staying in `Cc`.
+/// The same happens if there is no author address to reply to: headers
+/// without any usable address are treated as missing.
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let original = HeaderAddresses {
+///     from: Some(parse_address_list("Seoltóir <seoltoir@example.org>").unwrap()),
+///     to: Some(parse_address_list("mise@example.org, eile@example.org").unwrap()),
+///     cc: Some(parse_address_list("Eile <eile@example.org>").unwrap()),
+///     ..Default::default()
+/// };
+/// let me = [Contact::new("mise@example.org")];
+///
+/// let (to, cc) = reply_all(&original, &me, &ReplyOptions::default());
+///
+/// assert!(to.deep_eq(&vec![
+///     Contact::new("seoltoir@example.org").set_name("Seoltóir"),
+/// ].into()));
+/// assert!(cc.deep_eq(&vec![
+///     Contact::new("eile@example.org").set_name("Eile"),
+/// ].into()));
+/// ```
+pub fn reply_all(
+    original: &HeaderAddresses,
+    me: &[Contact],
+    options: &ReplyOptions,
+) -> (AddressList, AddressList) {
+    let empty = Contacts::new();
+
+    if options.use_mail_followup_to {
+        if let Some(followup) = usable(&original.mail_followup_to) {
+            return (collect(&[followup], me, &empty), empty.into());
+        }
+    }
+
+    let mail_reply_to = if options.use_mail_reply_to {
+        usable(&original.mail_reply_to)
+    } else {
+        None
+    };
+    let author = mail_reply_to
+        .or_else(|| usable(&original.reply_to))
+        .or_else(|| usable(&original.from));
+    let to = non_empty(&original.to);
+    let cc = non_empty(&original.cc);
+
+    let ours = author.is_none_or(|a| {
+        a.contacts()
+            .iter()
+            .filter(|c| !c.is_garbage())
+            .all(|c| me.contains(c))
+    });
+    let (to_list, cc_sources): (_, Vec<_>) = if ours {
+        // We wrote the original message, or there's no author to reply to,
+        // so we're replying to its recipients
+        (
+            collect(&to.into_iter().collect::<Vec<_>>(), me, &empty),
+            cc.into_iter().collect(),
+        )
+    } else {
+        (
+            collect(&author.into_iter().collect::<Vec<_>>(), me, &empty),
+            to.into_iter().chain(cc).collect(),
+        )
+    };
+    let cc_list = collect(&cc_sources, me, to_list.contacts());
+
+    (to_list, cc_list)
+}
@@ -0,0 +1,185 @@
+use crate::address_list::*;
+
+/// Where in an [`AddressList`] an [`InvisibleChar`] was found
+///
+/// [`AddressList`]: enum.AddressList.html
+/// [`InvisibleChar`]: struct.InvisibleChar.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    GroupName,
+    Name,
+    Comment,
+    Email,
+    /// The text of a [`GarbageContact`]
+    ///
+    /// [`GarbageContact`]: struct.GarbageContact.html
+    Garbage,
+}
+
+/// An invisible or text direction changing character
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvisibleChar {
+    /// Index of the contact, `None` for the group name
+    pub contact: Option<usize>,
+    pub field: Field,
+    /// Byte offset into the field, before any sanitisation
+    pub offset: usize,
+    pub character: char,
+}
+
+/// What [`AddressList::sanitise`] does with invisible characters
+///
+/// [`AddressList::sanitise`]: enum.AddressList.html#method.sanitise
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sanitisation {
+    /// Leave them in, only report them
+    Report,
+    /// Remove them
+    Strip,
+    /// Replace them with a visible `\u{…}` escape
+    Escape,
+}
+
+/// Characters that render as nothing, or change how the text around them
+/// renders
+///
+/// That's bidirectional controls (which can make `moc.evil@` look like
+/// `evil.com`), zero-width and other default ignorable characters, tag
+/// characters and control codes. Some of these do have legitimate uses, the
+/// zero-width non-joiner in Persian names for instance, so they are reported
+/// like everything else and it's up to the caller what to do about them.
+pub fn is_invisible(c: char) -> bool {
+    matches!(c,
+        '\u{0000}'..='\u{001F}'
+        | '\u{007F}'..='\u{009F}'
+        | '\u{00AD}'
+        | '\u{034F}'
+        | '\u{061C}'
+        | '\u{115F}'..='\u{1160}'
+        | '\u{17B4}'..='\u{17B5}'
+        | '\u{180B}'..='\u{180F}'
+        | '\u{200B}'..='\u{200F}'
+        | '\u{202A}'..='\u{202E}'
+        | '\u{2060}'..='\u{206F}'
+        | '\u{3164}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FEFF}'
+        | '\u{FFA0}'
+        | '\u{FFF0}'..='\u{FFF8}'
+        | '\u{1D173}'..='\u{1D17A}'
+        | '\u{E0000}'..='\u{E0FFF}'
+    )
+}
+
+/// Replace invisible characters in `text` with `\u{…}` escapes
+pub(crate) fn escape_invisible(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if is_invisible(c) {
+            escaped.push_str(&format!("\\u{{{:04X}}}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Record invisible characters of `text`, then apply `action` to it
+fn sanitise_text(
+    text: &mut String,
+    contact: Option<usize>,
+    field: Field,
+    action: Sanitisation,
+    found: &mut Vec<InvisibleChar>,
+) {
+    let before = found.len();
+    found.extend(text.char_indices().filter(|(_, c)| is_invisible(*c)).map(
+        |(offset, character)| InvisibleChar {
+            contact,
+            field,
+            offset,
+            character,
+        },
+    ));
+    if found.len() == before {
+        return;
+    }
+    match action {
+        Sanitisation::Report => {}
+        Sanitisation::Strip => text.retain(|c| !is_invisible(c)),
+        Sanitisation::Escape => *text = escape_invisible(text),
+    }
+}
+
+/// Like `sanitise_text`, but names and comments left empty are dropped
+fn sanitise_optional(
+    text: &mut Option<String>,
+    contact: Option<usize>,
+    field: Field,
+    action: Sanitisation,
+    found: &mut Vec<InvisibleChar>,
+) {
+    if let Some(t) = text {
+        sanitise_text(t, contact, field, action, found);
+        if t.trim().is_empty() {
+            *text = None;
+        }
+    }
+}
+
+impl AddressList {
+    /// Find invisible and bidirectional control characters, see
+    /// [`is_invisible`] for which ones
+    ///
+    /// [`is_invisible`]: fn.is_invisible.html
+    pub fn invisible_chars(&self) -> Vec<InvisibleChar> {
+        self.clone().sanitise(Sanitisation::Report)
+    }
+
+    /// Find invisible and bidirectional control characters in group name,
+    /// names, comments, addresses and garbage, and strip or escape them
+    ///
+    /// Returns what was found, with offsets into the original text. To only
+    /// make the output safe without changing the list, format it with `{:#}`
+    /// instead, which escapes them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let mut list = parse_address_list("\"Ainm\u{202E}txt.exe\" <ainm@example.org>").unwrap();
+    ///
+    /// assert_eq!(format!("{:#}", list), r#""Ainm\u{202E}txt.exe" <ainm@example.org>"#);
+    ///
+    /// let found = list.sanitise(Sanitisation::Strip);
+    /// assert_eq!(found, vec![InvisibleChar {
+    ///     contact: Some(0),
+    ///     field: Field::Name,
+    ///     offset: 4,
+    ///     character: '\u{202E}',
+    /// }]);
+    /// assert_eq!(list.to_string(), r#""Ainmtxt.exe" <ainm@example.org>"#);
+    /// assert!(list.invisible_chars().is_empty());
+    /// ```
+    pub fn sanitise(&mut self, action: Sanitisation) -> Vec<InvisibleChar> {
+        let mut found = Vec::new();
+        if let AddressList::Group(g) = self {
+            sanitise_text(&mut g.name, None, Field::GroupName, action, &mut found);
+        }
+        for (i, contact) in self.as_contacts_mut().iter_mut().enumerate() {
+            match contact {
+                Contact::Email(e) => {
+                    sanitise_optional(&mut e.name, Some(i), Field::Name, action, &mut found);
+                    sanitise_optional(&mut e.comment, Some(i), Field::Comment, action, &mut found);
+                    sanitise_text(&mut e.email, Some(i), Field::Email, action, &mut found);
+                }
+                Contact::Garbage(g) => {
+                    let mut text = g.comment().unwrap().clone();
+                    sanitise_text(&mut text, Some(i), Field::Garbage, action, &mut found);
+                    *g = GarbageContact::new(text);
+                }
+            }
+        }
+        found
+    }
+}
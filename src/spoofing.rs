@@ -0,0 +1,221 @@
+use lazy_static::*;
+use regex::Regex;
+use unicode_security::skeleton;
+
+use crate::address_list::*;
+use crate::equivalence::{AddressEquivalence, Canonicalised};
+use crate::extract::{extract_contacts, ExtractOptions};
+use crate::psl::PublicSuffixList;
+
+lazy_static! {
+    static ref DOMAIN: Regex =
+        Regex::new(r"(?:[\p{L}\p{N}](?:[\p{L}\p{N}-]*[\p{L}\p{N}])?\.)+\p{L}{2,}").unwrap();
+}
+
+/// A display name that may only be used by addresses at certain domains
+///
+/// Names are matched case-insensitively and by their UTS #39 skeleton, so
+/// `Ρaypal Support` (with a Greek `Ρ`) matches a protected `PayPal`.
+#[derive(Debug, Clone)]
+pub struct ProtectedName {
+    name: String,
+    domains: Vec<String>,
+}
+
+impl ProtectedName {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            domains: Vec::new(),
+        }
+    }
+
+    /// Domains allowed to use the name, subdomains of these are allowed too
+    pub fn set_domains<T>(mut self, domains: &[T]) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.domains = domains.iter().map(|d| ascii_domain(d.as_ref())).collect();
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn allows(&self, domain: &str) -> bool {
+        self.domains.iter().any(|d| same_organisation(d, domain))
+    }
+}
+
+/// Knobs for [`EmailContact::spoofing`]
+///
+/// [`EmailContact::spoofing`]: struct.EmailContact.html#method.spoofing
+#[derive(Debug, Clone, Default)]
+pub struct SpoofingOptions {
+    protected_names: Vec<ProtectedName>,
+}
+
+impl SpoofingOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: ProtectedName) {
+        self.protected_names.push(name);
+    }
+
+    pub fn set_protected_name(mut self, name: ProtectedName) -> Self {
+        self.add(name);
+        self
+    }
+
+    pub fn protected_names(&self) -> &[ProtectedName] {
+        &self.protected_names
+    }
+}
+
+/// A reason to distrust the display name of a contact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Spoofing {
+    /// The name contains an address other than the contact's own, e.g.
+    /// `"ceo@example.com" <attacker@evil.example>`
+    AddressInName(String),
+    /// The name mentions a domain other than the contact's own, e.g.
+    /// `"Example.com Support" <help@evil.example>`
+    DomainInName(String),
+    /// The name matches a [`ProtectedName`] (given here), but the address is
+    /// not at one of its domains
+    ///
+    /// [`ProtectedName`]: struct.ProtectedName.html
+    ProtectedName(String),
+}
+
+fn ascii_domain(domain: &str) -> String {
+    let domain = domain.trim_end_matches('.');
+    idna::domain_to_ascii(domain).unwrap_or_else(|_| domain.to_lowercase())
+}
+
+/// Whether one domain is the other or a subdomain of it
+fn same_organisation(a: &str, b: &str) -> bool {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    longer == shorter
+        || longer
+            .strip_suffix(shorter)
+            .is_some_and(|rest| rest.ends_with('.'))
+}
+
+/// Lower-cased skeleton with everything but letters and digits collapsed
+/// into single spaces, padded so whole words can be found with `contains`
+fn comparable(name: &str) -> String {
+    let skeleton: String = skeleton(&name.to_lowercase()).collect();
+    let words: Vec<_> = skeleton
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+impl EmailContact {
+    /// Check whether the display name might be used to impersonate somebody
+    ///
+    /// The name is searched for addresses and domains that differ from the
+    /// actual address, domains count as the same if one is a subdomain of
+    /// the other. Only dotted words ending in a top-level domain from the
+    /// [`PublicSuffixList`] are taken for domains. Obfuscated addresses like
+    /// `Support at example.org` are often just a way of writing the domain,
+    /// so they're only reported if their domain differs. Contacts without a
+    /// name never produce findings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = parse_contact(r#""ceo@example.com" <ceo@evil.example>"#).unwrap();
+    /// let Contact::Email(contact) = contact else { unreachable!() };
+    ///
+    /// let options = SpoofingOptions::new()
+    ///     .set_protected_name(ProtectedName::new("Example CEO").set_domains(&["example.com"]));
+    ///
+    /// assert_eq!(
+    ///     contact.spoofing(&options),
+    ///     vec![Spoofing::AddressInName("ceo@example.com".into())],
+    /// );
+    ///
+    /// let contact = EmailContact::new("boss@evil.example").set_name("EXAMPLE  CEO");
+    /// assert_eq!(
+    ///     contact.spoofing(&options),
+    ///     vec![Spoofing::ProtectedName("Example CEO".into())],
+    /// );
+    ///
+    /// let contact = EmailContact::new("ceo@mail.example.com").set_name("Example CEO");
+    /// assert!(contact.spoofing(&options).is_empty());
+    /// ```
+    ///
+    /// [`PublicSuffixList`]: struct.PublicSuffixList.html
+    pub fn spoofing(&self, options: &SpoofingOptions) -> Vec<Spoofing> {
+        let mut found = Vec::new();
+        let name = match self.name() {
+            Some(name) => name,
+            None => return found,
+        };
+        let email = self.email().unwrap();
+        let domain = self.domain().map(ascii_domain);
+
+        let extract = ExtractOptions::new().set_names(false).set_deobfuscate(true);
+        let addresses = extract_contacts(name, &extract);
+        for a in &addresses {
+            let address = a.contact.email().unwrap();
+            let same_domain = a
+                .contact
+                .domain()
+                .map(ascii_domain)
+                .is_some_and(|mentioned| {
+                    domain
+                        .as_ref()
+                        .is_some_and(|own| same_organisation(own, &mentioned))
+                });
+            let same_mailbox = same_domain
+                && a.contact.local_part().map(str::to_lowercase)
+                    == self.local_part().map(str::to_lowercase);
+            // An obfuscated address may just be spelling out the domain
+            let own_domain = same_domain && a.deobfuscated;
+            if !same_mailbox && !own_domain && !Canonicalised.equivalent(address, email) {
+                found.push(Spoofing::AddressInName(address.clone()));
+            }
+        }
+        for d in DOMAIN.find_iter(name) {
+            let inside_address = addresses
+                .iter()
+                .any(|a| a.address_span.start <= d.start() && d.end() <= a.address_span.end);
+            // Dotted words like `node.js` or a `jane.doe` local part aren't
+            // domains
+            let tld = d.as_str().rsplit('.').next().unwrap();
+            let own_local = self
+                .local_part()
+                .is_some_and(|l| l.to_lowercase() == d.as_str().to_lowercase());
+            if inside_address || own_local || !PublicSuffixList::builtin().is_tld(tld) {
+                continue;
+            }
+            let mentioned = ascii_domain(d.as_str());
+            if !domain
+                .as_ref()
+                .is_some_and(|own| same_organisation(own, &mentioned))
+            {
+                found.push(Spoofing::DomainInName(d.as_str().into()));
+            }
+        }
+
+        let comparable_name = comparable(name);
+        for protected in &options.protected_names {
+            let allowed = domain.as_ref().is_some_and(|d| protected.allows(d));
+            if !allowed && comparable_name.contains(&comparable(&protected.name)) {
+                found.push(Spoofing::ProtectedName(protected.name.clone()));
+            }
+        }
+        found
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use crate::address_list::*;
+use crate::equivalence::*;
+
+/// Where the sub-address ("tag") of a local part starts, e.g. the `+` in
+/// `user+tag@example.org`
+///
+/// Providers don't agree on a separator, so it can be configured per domain.
+/// When used as an [`AddressEquivalence`], addresses are compared with their
+/// tags stripped and their domain lower-cased.
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_address_list::*;
+/// let rules = Subaddressing::default().set_domain_separator("example.net", "-");
+///
+/// let plus = EmailContact::new("user+ticket123@example.org");
+/// let dash = EmailContact::new("user-ticket123@example.net");
+///
+/// assert_eq!(plus.subaddress_with(&rules), Some("ticket123"));
+/// assert_eq!(dash.subaddress_with(&rules), Some("ticket123"));
+/// assert!(rules.equivalent("user+a@example.org", "user+b@EXAMPLE.org"));
+/// ```
+///
+/// [`AddressEquivalence`]: trait.AddressEquivalence.html
+#[derive(Debug, Clone)]
+pub struct Subaddressing {
+    separator: String,
+    domains: HashMap<String, String>,
+}
+
+impl Subaddressing {
+    /// Use `separator` for all domains without a separator of their own
+    pub fn new<T>(separator: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            separator: separator.as_ref().into(),
+            domains: HashMap::new(),
+        }
+    }
+
+    /// Use `separator` for addresses at `domain`
+    pub fn set_domain_separator<D, S>(mut self, domain: D, separator: S) -> Self
+    where
+        D: AsRef<str>,
+        S: AsRef<str>,
+    {
+        self.domains
+            .insert(domain.as_ref().to_lowercase(), separator.as_ref().into());
+        self
+    }
+
+    /// The separator used for addresses at `domain`
+    pub fn separator(&self, domain: &str) -> &str {
+        self.domains
+            .get(&domain.to_lowercase())
+            .unwrap_or(&self.separator)
+    }
+
+    /// Split a local part into the part before the separator and the tag
+    fn split<'a>(&self, local: &'a str, domain: &str) -> (&'a str, Option<&'a str>) {
+        match split_unquoted(local, self.separator(domain)) {
+            Some((base, tag)) => (base, Some(tag)),
+            None => (local, None),
+        }
+    }
+}
+
+/// Split a local part at the first `separator` that isn't quoted and has
+/// something in front of it
+///
+/// A separator inside quotes is just part of the mailbox name, so
+/// `"a+b"@example.org` has no tag.
+pub(crate) fn split_unquoted<'a>(local: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    if separator.is_empty() {
+        return None;
+    }
+    let (mut quoted, mut escaped) = (false, false);
+    for (i, c) in local.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if quoted && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            quoted = !quoted;
+        } else if !quoted && i > 0 && local[i..].starts_with(separator) {
+            return Some((&local[..i], &local[i + separator.len()..]));
+        }
+    }
+    None
+}
+
+/// `+`, the most common separator
+impl Default for Subaddressing {
+    fn default() -> Self {
+        Self::new("+")
+    }
+}
+
+impl AddressEquivalence for Subaddressing {
+    fn key(&self, email: &str) -> String {
+        match split_key(email) {
+            Some((local, domain)) => {
+                let (base, _) = self.split(local, domain);
+                format!("{}@{}", base, domain.to_lowercase())
+            }
+            None => email.into(),
+        }
+    }
+}
+
+impl EmailContact {
+    /// The sub-address ("plus tag") of the local part, if there is one
+    ///
+    /// Shorthand for [`subaddress_with`] using `+` as separator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("user+ticket123@example.org");
+    ///
+    /// assert_eq!(contact.subaddress(), Some("ticket123"));
+    /// assert_eq!(EmailContact::new("user@example.org").subaddress(), None);
+    /// ```
+    ///
+    /// [`subaddress_with`]: #method.subaddress_with
+    pub fn subaddress(&self) -> Option<&str> {
+        self.subaddress_with(&Subaddressing::default())
+    }
+
+    /// The sub-address of the local part according to `rules`
+    ///
+    /// Empty tags (`user+@example.org`) are treated as no tag at all.
+    pub fn subaddress_with(&self, rules: &Subaddressing) -> Option<&str> {
+        let (local, domain) = (self.local_part()?, self.domain()?);
+        rules.split(local, domain).1.filter(|tag| !tag.is_empty())
+    }
+
+    /// Remove the sub-address, including its separator
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("user+ticket123@example.org").set_name("User");
+    /// let stripped = contact.strip_subaddress(&Subaddressing::default());
+    ///
+    /// assert!(stripped.deep_eq(&EmailContact::new("user@example.org").set_name("User")));
+    /// ```
+    pub fn strip_subaddress(self, rules: &Subaddressing) -> Self {
+        self.replace_subaddress(None, rules)
+    }
+
+    /// Replace the sub-address with `tag`, or add it if there is none
+    ///
+    /// No tag is added to a quoted local part, as it would end up outside the
+    /// quotes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let rules = Subaddressing::new("-");
+    /// let contact = EmailContact::new("user@example.org").set_subaddress("ticket123", &rules);
+    ///
+    /// assert_eq!(contact.email().unwrap(), "user-ticket123@example.org");
+    /// ```
+    pub fn set_subaddress<T>(self, tag: T, rules: &Subaddressing) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.replace_subaddress(Some(tag.as_ref()), rules)
+    }
+
+    fn replace_subaddress(self, tag: Option<&str>, rules: &Subaddressing) -> Self {
+        let email = match (self.local_part(), self.domain()) {
+            (Some(local), Some(domain)) => {
+                let (base, _) = rules.split(local, domain);
+                match tag {
+                    Some(_) if base.ends_with('"') => return self,
+                    Some(tag) => format!("{}{}{}@{}", base, rules.separator(domain), tag, domain),
+                    None => format!("{}@{}", base, domain),
+                }
+            }
+            _ => return self,
+        };
+        self.set_email(email)
+    }
+}
+
+impl AddressList {
+    /// Strip the sub-addresses of all contacts, so tagged addresses can be
+    /// de-duplicated with their untagged counterparts
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let mut list = parse_address_list(
+    ///     "user+a@example.org, user+b@example.org, user@example.org",
+    /// ).unwrap();
+    ///
+    /// list.strip_subaddresses(&Subaddressing::default());
+    /// list.dedup(&Exact);
+    ///
+    /// assert!(list.deep_eq(&vec![Contact::new("user@example.org")].into()));
+    /// ```
+    pub fn strip_subaddresses(&mut self, rules: &Subaddressing) {
+        for contact in self.as_contacts_mut() {
+            if let Contact::Email(e) = contact {
+                *e = e.clone().strip_subaddress(rules);
+            }
+        }
+    }
+}
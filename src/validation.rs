@@ -0,0 +1,183 @@
+use lazy_static::*;
+use regex::Regex;
+
+use crate::address_list::*;
+use crate::domain::AddressLiteral;
+
+lazy_static! {
+    // RFC 5322 dot-atom, with RFC 6532's UTF-8 extension
+    static ref DOT_ATOM: Regex =
+        Regex::new(r"^[\w!#$%&'*+/=?^`{|}~-]+(\.[\w!#$%&'*+/=?^`{|}~-]+)*$").unwrap();
+    // RFC 5322 quoted-string without folding whitespace, with RFC 6532's UTF-8 extension
+    static ref QUOTED_STRING: Regex = Regex::new(r#"^"([^"\\\p{Cc}]|\\[^\p{Cc}]|[ \t])*"$"#).unwrap();
+    static ref HTML5_LOCAL: Regex = Regex::new(r"^[a-zA-Z0-9.!#$%&'*+/=?^_`{|}~-]+$").unwrap();
+    // Letters, digits and hyphens, as in HTML5 and RFC 5890
+    static ref LDH_LABEL: Regex = Regex::new(r"^[a-zA-Z0-9]([a-zA-Z0-9-]*[a-zA-Z0-9])?$").unwrap();
+}
+
+/// How strict [`EmailContact::validate`] should be
+///
+/// [`EmailContact::validate`]: struct.EmailContact.html#method.validate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationLevel {
+    /// Anything the permissive grammar can split into local part and domain
+    Permissive,
+    /// Syntactically valid `addr-spec` according to RFC 5322 (and RFC 6532
+    /// for UTF-8)
+    Rfc5322Syntax,
+    /// RFC 5322 syntax that an SMTP server could accept according to RFC 5321:
+    /// length limits, host names made of letters, digits and hyphens (after
+    /// IDNA conversion), no single-label domains and valid address literals
+    Rfc5321Deliverable,
+    /// What an HTML5 `<input type="email">` accepts
+    Html5Input,
+}
+
+/// Reasons for an address not to pass [`EmailContact::validate`]
+///
+/// [`EmailContact::validate`]: struct.EmailContact.html#method.validate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The address can't be split into local part and domain at all
+    Unparseable,
+    InvalidLocalPart,
+    InvalidDomain,
+    /// An address literal that is neither an IP address nor a general
+    /// address literal
+    InvalidAddressLiteral,
+    /// Local part longer than 64 octets
+    LocalPartTooLong(usize),
+    /// Address longer than 254 octets
+    AddressTooLong(usize),
+    /// Domain longer than 255 octets
+    DomainTooLong(usize),
+    EmptyLabel,
+    /// Domain label longer than 63 octets
+    LabelTooLong(String),
+    /// A domain without any dots, like `localhost`
+    SingleLabelDomain,
+}
+
+/// Check every label of `domain` against `valid`, and against the 63 octet
+/// limit of RFC 1035 if `limit_length` is set
+fn check_labels(domain: &str, valid: &Regex, limit_length: bool, violations: &mut Vec<Violation>) {
+    let domain = domain.strip_suffix('.').unwrap_or(domain);
+    let mut invalid = false;
+    for label in domain.split('.') {
+        if label.is_empty() {
+            push(violations, Violation::EmptyLabel);
+        } else if limit_length && label.len() > 63 {
+            violations.push(Violation::LabelTooLong(label.into()));
+        } else if !valid.is_match(label) {
+            invalid = true;
+        }
+    }
+    if invalid {
+        push(violations, Violation::InvalidDomain);
+    }
+}
+
+fn push(violations: &mut Vec<Violation>, violation: Violation) {
+    if !violations.contains(&violation) {
+        violations.push(violation);
+    }
+}
+
+fn validate_syntax(local: &str, domain: &str, violations: &mut Vec<Violation>) {
+    if !DOT_ATOM.is_match(local) && !QUOTED_STRING.is_match(local) {
+        push(violations, Violation::InvalidLocalPart);
+    }
+    if domain.starts_with('[') {
+        return;
+    }
+    check_labels(domain, &DOT_ATOM, false, violations);
+}
+
+fn validate_deliverable(email: &str, local: &str, domain: &str, violations: &mut Vec<Violation>) {
+    validate_syntax(local, domain, violations);
+    if local.len() > 64 {
+        violations.push(Violation::LocalPartTooLong(local.len()));
+    }
+    if email.len() > 254 {
+        violations.push(Violation::AddressTooLong(email.len()));
+    }
+    if domain.starts_with('[') {
+        if AddressLiteral::parse(domain).is_none() {
+            violations.push(Violation::InvalidAddressLiteral);
+        }
+        return;
+    }
+    let ascii = match idna::domain_to_ascii(domain) {
+        Ok(ascii) => ascii,
+        Err(_) => {
+            push(violations, Violation::InvalidDomain);
+            domain.into()
+        }
+    };
+    if ascii.len() > 255 {
+        violations.push(Violation::DomainTooLong(ascii.len()));
+    }
+    check_labels(&ascii, &LDH_LABEL, true, violations);
+    if !ascii.trim_end_matches('.').contains('.') {
+        violations.push(Violation::SingleLabelDomain);
+    }
+}
+
+fn validate_html5(local: &str, domain: &str, violations: &mut Vec<Violation>) {
+    if !HTML5_LOCAL.is_match(local) {
+        violations.push(Violation::InvalidLocalPart);
+    }
+    if domain.ends_with('.') {
+        push(violations, Violation::EmptyLabel);
+    }
+    check_labels(domain, &LDH_LABEL, true, violations);
+}
+
+impl EmailContact {
+    /// Check the address against `level`, returning all violations found
+    ///
+    /// An empty list means the address is valid at that level.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let contact = EmailContact::new("postmaster@localhost");
+    ///
+    /// assert!(contact.validate(ValidationLevel::Html5Input).is_empty());
+    /// assert_eq!(
+    ///     contact.validate(ValidationLevel::Rfc5321Deliverable),
+    ///     vec![Violation::SingleLabelDomain],
+    /// );
+    ///
+    /// let long = EmailContact::new(format!("{}@example..org", "a".repeat(65)));
+    ///
+    /// assert_eq!(
+    ///     long.validate(ValidationLevel::Rfc5321Deliverable),
+    ///     vec![Violation::EmptyLabel, Violation::LocalPartTooLong(65)],
+    /// );
+    /// ```
+    pub fn validate(&self, level: ValidationLevel) -> Vec<Violation> {
+        let (local, domain) = match (self.local_part(), self.domain()) {
+            (Some(local), Some(domain)) => (local, domain),
+            _ => return vec![Violation::Unparseable],
+        };
+        let mut violations = Vec::new();
+        match level {
+            ValidationLevel::Permissive => {}
+            ValidationLevel::Rfc5322Syntax => validate_syntax(local, domain, &mut violations),
+            ValidationLevel::Rfc5321Deliverable => {
+                validate_deliverable(self.email().unwrap(), local, domain, &mut violations)
+            }
+            ValidationLevel::Html5Input => validate_html5(local, domain, &mut violations),
+        }
+        violations
+    }
+
+    /// Shorthand for checking that [`validate`] doesn't find any violations
+    ///
+    /// [`validate`]: #method.validate
+    pub fn is_valid(&self, level: ValidationLevel) -> bool {
+        self.validate(level).is_empty()
+    }
+}
@@ -0,0 +1,148 @@
+use lazy_static::*;
+use regex::Regex;
+
+use crate::address_list::*;
+use crate::classify::AddressCategory;
+use crate::equivalence::{AddressEquivalence, Canonicalised};
+use crate::extract::{extract_contacts, ExtractOptions};
+
+lazy_static! {
+    static ref VIA_NAME: Regex = Regex::new(r"(?i)^\s*(.+?)\s+via\s+(.+?)\s*$").unwrap();
+    static ref VIA_COMMENT: Regex = Regex::new(r"(?i)^\s*via\s+(.+?)\s*$").unwrap();
+}
+
+/// A `From` that a mailing list rewrote to its own address, as found by
+/// [`EmailContact::list_rewrite`]
+///
+/// [`EmailContact::list_rewrite`]: struct.EmailContact.html#method.list_rewrite
+#[derive(Debug, Clone)]
+pub struct ListRewrite {
+    /// The name of the list, as far as it's given
+    pub list_name: String,
+    /// The original author's name
+    pub name: Option<String>,
+    /// The original author, if their address could be recovered
+    pub original: Option<EmailContact>,
+}
+
+fn unquote(name: &str) -> &str {
+    let name = name.trim();
+    for quote in ['\'', '"'] {
+        if let Some(inner) = name.strip_prefix(quote).and_then(|n| n.strip_suffix(quote)) {
+            return inner.trim();
+        }
+    }
+    name
+}
+
+/// The first address mentioned in `text`, obfuscated ones included
+fn address_in(text: &str) -> Option<EmailContact> {
+    let options = ExtractOptions::new().set_names(false).set_deobfuscate(true);
+    extract_contacts(text, &options)
+        .into_iter()
+        .next()
+        .map(|e| EmailContact::new(e.contact.email().unwrap()))
+}
+
+impl EmailContact {
+    /// Check whether a mailing list rewrote this contact, e.g. to get past
+    /// DMARC, and recover the original author
+    ///
+    /// Recognised are names like `Jane via Dev List` or `'Jane' via Group`,
+    /// and a `via …` comment. As " via " also occurs in ordinary names, such
+    /// a name only counts if something else points to a list: the address
+    /// classifies as a mailing list or bounce address, `reply_to` names
+    /// somebody else, or the author is quoted (Google Groups) or an address.
+    ///
+    /// The original address is taken from the name or comment if it's in
+    /// there (Mailman uses `jane at example.org via Dev` when the author had
+    /// no name), otherwise from `reply_to`: the contact with the author's
+    /// name, or the only one that isn't the list itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use email_address_list::*;
+    /// let from = parse_contact("\"Jane via Dev List\" <dev@lists.example.org>").unwrap();
+    /// let reply_to = parse_address_list("Jane <jane@example.org>").unwrap();
+    /// let Contact::Email(from) = from else { unreachable!() };
+    ///
+    /// let rewrite = from.list_rewrite(Some(&reply_to)).unwrap();
+    /// assert_eq!(rewrite.list_name, "Dev List");
+    /// assert_eq!(rewrite.name.unwrap(), "Jane");
+    /// assert!(rewrite.original.unwrap()
+    ///     .deep_eq(&EmailContact::new("jane@example.org").set_name("Jane")));
+    ///
+    /// assert!(EmailContact::new("jane@example.org").set_name("Jane").list_rewrite(None).is_none());
+    /// assert!(EmailContact::new("mario@example.it")
+    ///     .set_name("Mario Rossi via Roma")
+    ///     .list_rewrite(None)
+    ///     .is_none());
+    /// ```
+    pub fn list_rewrite(&self, reply_to: Option<&AddressList>) -> Option<ListRewrite> {
+        let (author, list_name) = if let Some(c) = self.name().and_then(|n| VIA_NAME.captures(n)) {
+            let listish = self.classify().iter().any(|category| {
+                matches!(
+                    category,
+                    AddressCategory::MailingList | AddressCategory::Bounce
+                )
+            });
+            let other_reply_to = reply_to.is_some_and(|r| {
+                r.contacts()
+                    .iter()
+                    .filter_map(|c| c.email())
+                    .any(|e| !Canonicalised.equivalent(e, self.email().unwrap()))
+            });
+            let known_shape = unquote(&c[1]) != c[1].trim() || address_in(&c[1]).is_some();
+            if !(listish || other_reply_to || known_shape) {
+                return None;
+            }
+            (Some(unquote(&c[1]).to_string()), c[2].to_string())
+        } else {
+            let c = self.comment().and_then(|c| VIA_COMMENT.captures(c))?;
+            (
+                self.name().map(|n| unquote(n).to_string()),
+                c[1].to_string(),
+            )
+        };
+
+        let mut name = author.filter(|a| !a.is_empty());
+        let mut original = None;
+        if let Some(address) = name.as_deref().and_then(address_in) {
+            // The name was just the author's address
+            original = Some(address);
+            name = None;
+        }
+        if original.is_none() {
+            original = self.comment().and_then(|c| address_in(c));
+        }
+        if original.is_none() {
+            let candidates: Vec<_> = reply_to
+                .map(|r| r.contacts().iter())
+                .into_iter()
+                .flatten()
+                .filter_map(|c| match c {
+                    Contact::Email(e) if e != self => Some(e),
+                    _ => None,
+                })
+                .collect();
+            let by_name = candidates
+                .iter()
+                .find(|c| name.is_some() && c.name().map(|n| unquote(n)) == name.as_deref());
+            original = match (by_name, candidates.as_slice()) {
+                (Some(c), _) | (None, [c]) => Some(EmailContact::new(c.email().unwrap())),
+                _ => None,
+            };
+        }
+        let original = original.map(|o| match &name {
+            Some(n) => o.set_name(n),
+            None => o,
+        });
+
+        Some(ListRewrite {
+            list_name,
+            name,
+            original,
+        })
+    }
+}
@@ -3,6 +3,7 @@ use email_address_list::*;
 use std::process::Command;
 
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn big_list_of_naughty_strings() {
     let naughty = Command::new("curl")
         .args(["https://raw.githubusercontent.com/minimaxir/big-list-of-naughty-strings/master/blns.txt"])
@@ -70,6 +71,11 @@ fn equality() {
             "<oneaddress@without.delimiter> <twoaddresses@without.delimiter>, ",
             "oneaddress@with.delimiter",
         ),
+        concat!(
+            r#""Ime Priimek" <enaslov@example.org;primer@example.org>, "#,
+            "Oblikovan, Nepravilno <nepravilno.oblikovan@example.org,>>, ",
+            "<'napačno.oblikovan@example.org'>",
+        ),
     ];
     let address_lists: Vec<AddressList> = vec![
         Group::new("Garbage")
@@ -151,6 +157,13 @@ fn equality() {
             Contact::new("oneaddress@with.delimiter"),
         ]
         .into(),
+        vec![
+            Contact::new("enaslov@example.org").set_name("Ime Priimek"),
+            Contact::new("primer@example.org"),
+            Contact::new("nepravilno.oblikovan@example.org").set_name("Oblikovan, Nepravilno"),
+            Contact::new("napačno.oblikovan@example.org"),
+        ]
+        .into(),
     ];
     assert!(literals.len() == address_lists.len());
     for (i, address_list) in address_lists.iter().enumerate() {
@@ -170,3 +183,1277 @@ fn equality() {
         assert!(address_list != &other, "!= {:?}", other);
     }
 }
+
+#[test]
+fn reply_all_recipients() {
+    let me = [Contact::new("me@example.org")];
+    let mut original = HeaderAddresses {
+        from: Some(parse_address_list("Author <author@example.org>").unwrap()),
+        reply_to: Some(parse_address_list("replies@example.org").unwrap()),
+        to: Some(parse_address_list("Team: me@example.org, colleague@example.org;").unwrap()),
+        cc: Some(parse_address_list("replies@example.org, <me@example.org>").unwrap()),
+        ..Default::default()
+    };
+
+    let (to, cc) = reply_all(&original, &me, &ReplyOptions::default());
+    assert!(to.deep_eq(&vec![Contact::new("replies@example.org")].into()));
+    assert!(cc.deep_eq(
+        &Group::new("Team")
+            .set_contacts(vec![Contact::new("colleague@example.org")])
+            .into()
+    ));
+
+    original.mail_followup_to =
+        Some(parse_address_list("list@example.org, me@example.org").unwrap());
+    let (to, cc) = reply_all(&original, &me, &ReplyOptions::default());
+    assert!(to.deep_eq(&vec![Contact::new("list@example.org")].into()));
+    assert!(cc.is_empty());
+
+    let options = ReplyOptions::new().set_use_mail_followup_to(false);
+    original.from = Some(parse_address_list("Me <me@example.org>").unwrap());
+    original.reply_to = None;
+    let (to, cc) = reply_all(&original, &me, &options);
+    assert!(to.deep_eq(
+        &Group::new("Team")
+            .set_contacts(vec![Contact::new("colleague@example.org")])
+            .into()
+    ));
+    assert!(cc.deep_eq(&vec![Contact::new("replies@example.org")].into()));
+
+    // Headers without a usable address are skipped, without any author the
+    // recipients are carried over
+    original.from = Some(parse_address_list("garbage").unwrap());
+    let (to, cc) = reply_all(&original, &me, &options);
+    assert!(to.deep_eq(
+        &Group::new("Team")
+            .set_contacts(vec![Contact::new("colleague@example.org")])
+            .into()
+    ));
+    assert!(cc.deep_eq(&vec![Contact::new("replies@example.org")].into()));
+
+    original.from = Some(parse_address_list("Author <author@example.org>").unwrap());
+    original.reply_to = Some(parse_address_list("garbage").unwrap());
+    original.mail_reply_to = Some(parse_address_list("more garbage").unwrap());
+    let (to, cc) = reply_all(&original, &me, &options);
+    assert!(to.deep_eq(&vec![Contact::new("author@example.org").set_name("Author")].into()));
+    assert!(cc.deep_eq(
+        &vec![
+            Contact::new("colleague@example.org"),
+            Contact::new("replies@example.org")
+        ]
+        .into()
+    ));
+}
+
+#[test]
+fn set_operations() {
+    let mut list = parse_address_list(
+        "a@example.org, b@EXAMPLE.org, B@example.org, Bee <b@example.org>, c@example.org",
+    )
+    .unwrap();
+    let other = parse_address_list("b@example.org, d@example.org").unwrap();
+
+    let mut exact = list.clone();
+    exact.dedup(&Exact);
+    assert_eq!(exact.len(), 5);
+    let mut domain = list.clone();
+    domain.dedup(&CaseInsensitiveDomain);
+    assert_eq!(domain.len(), 4);
+    let mut canonical = list.clone();
+    canonical.dedup(&Canonicalised);
+    assert_eq!(canonical.len(), 3);
+
+    let mut intersection = list.clone();
+    intersection.intersection(&other, &CaseInsensitiveDomain);
+    assert_eq!(intersection.len(), 2);
+
+    let mut difference = list.clone();
+    difference.difference(&other, &Canonicalised);
+    assert!(difference
+        .deep_eq(&vec![Contact::new("a@example.org"), Contact::new("c@example.org")].into()));
+
+    assert!(list.remove(&Contact::new("c@example.org"), &Exact));
+    assert!(!list.remove(&Contact::new("c@example.org"), &Exact));
+    list.union(&other, &Exact);
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.contacts()[4].email().unwrap(), "d@example.org");
+
+    let mut vec = vec![Contact::new("e@example.org"), Contact::new("e@example.org")];
+    Contactsish::dedup(&mut vec, &Exact);
+    assert_eq!(vec.len(), 1);
+}
+
+#[test]
+fn contact_identity() {
+    use std::collections::{BTreeSet, HashSet};
+
+    let lower = Contact::new("Local@example.org");
+    let upper = Contact::new("Local@EXAMPLE.ORG").set_name("Name");
+    let other_local = Contact::new("local@example.org");
+    assert_eq!(lower, upper);
+    assert_ne!(lower, other_local);
+
+    let garbage = parse_contact("Mist").unwrap();
+    let other_garbage = parse_contact("Moar Mist").unwrap();
+    assert_ne!(garbage, other_garbage);
+    assert_eq!(garbage, parse_contact("Mist").unwrap());
+    assert_ne!(garbage, lower);
+
+    // Addresses the grammar doesn't accept still compare domains
+    // case-insensitively, quoted local parts are kept intact
+    assert_eq!(
+        Contact::new("a b@EXAMPLE.org"),
+        Contact::new("a b@example.org")
+    );
+    assert_eq!(
+        Contact::new(r#""a@b"@EXAMPLE.org"#),
+        Contact::new(r#""a@b"@example.org"#)
+    );
+    assert_ne!(Contact::new(r#""a@B""#), Contact::new(r#""a@b""#));
+
+    let hashed: HashSet<Contact> = vec![
+        lower.clone(),
+        upper.clone(),
+        other_local.clone(),
+        garbage.clone(),
+        other_garbage.clone(),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(hashed.len(), 4);
+
+    let ordered: BTreeSet<Contact> = vec![
+        other_garbage.clone(),
+        garbage.clone(),
+        other_local.clone(),
+        upper,
+        lower.clone(),
+        Contact::new("a@example.org"),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        ordered.into_iter().collect::<Vec<_>>(),
+        vec![
+            lower,
+            Contact::new("a@example.org"),
+            other_local,
+            garbage,
+            other_garbage,
+        ]
+    );
+}
+
+#[test]
+fn deep_equality() {
+    let contact = Contact::new("email@example.org")
+        .set_name("Name")
+        .set_comment("Comment");
+    let other_name = contact.clone().set_name("Other Name");
+    let other_comment = contact.clone().set_comment("Other Comment");
+    let shared_name = Contact::new("other@example.org").set_name("Name");
+
+    assert!(contact.deep_eq(&contact.clone()));
+    assert!(contact.deep_ne(&other_name));
+    assert!(contact.deep_ne(&other_comment));
+    assert!(contact.deep_ne(&shared_name));
+    assert!(contact.deep_ne(&parse_contact("Mist").unwrap()));
+    assert_eq!(contact.deep_diff(&other_name), vec![Difference::Name]);
+    assert_eq!(contact.deep_diff(&other_comment), vec![Difference::Comment]);
+    assert_eq!(
+        contact.deep_diff(&shared_name),
+        vec![Difference::Email, Difference::Comment]
+    );
+
+    let list: AddressList = Group::new("Group")
+        .set_contacts(vec![contact.clone(), shared_name.clone()])
+        .into();
+    let other: AddressList = Group::new("Other Group")
+        .set_contacts(vec![contact.clone(), other_name])
+        .into();
+    assert!(list.deep_ne(&other));
+    assert_eq!(
+        list.deep_diff(&other),
+        vec![
+            Difference::GroupName,
+            Difference::Contact(
+                1,
+                vec![Difference::Email, Difference::Name, Difference::Comment]
+            ),
+        ]
+    );
+    let contacts: AddressList = vec![contact.clone()].into();
+    assert_eq!(list.deep_diff(&contacts), vec![Difference::Kind]);
+    assert_eq!(
+        contacts.deep_diff(&vec![contact, shared_name].into()),
+        vec![Difference::Length]
+    );
+}
+
+#[test]
+fn equivalence_policies() {
+    let list = parse_address_list("Jane.Doe@Gmail.com, info@bücher.example").unwrap();
+
+    let folded = Contact::new("janedoe+receipts@googlemail.com");
+    assert!(!list.contains_with(&folded, &Exact));
+    assert!(!list.contains_with(&folded, &Canonicalised));
+    assert!(list.contains_with(&folded, &ProviderAware));
+
+    let punycode = Contact::new("info@xn--bcher-kva.example");
+    assert!(!list.contains(&punycode));
+    assert!(list.contains_with(&punycode, &IdnaNormalised));
+
+    let upper = Contact::new("Jane.Doe@GMAIL.COM");
+    assert!(list.contains(&upper));
+    assert!(!list.contains_with(&upper, &Exact));
+    assert!(upper.eq_with(&list.contacts()[0], &CaseInsensitiveDomain));
+    assert!(!upper.eq_with(&list.contacts()[0], &Exact));
+}
+
+#[test]
+fn subaddressing() {
+    let rules = Subaddressing::default().set_domain_separator("Example.NET", "-");
+
+    let plus = EmailContact::new("user+tag@example.org").set_name("User");
+    let dash = EmailContact::new("user-tag@example.net");
+    assert_eq!(plus.subaddress(), Some("tag"));
+    assert_eq!(dash.subaddress(), None);
+    assert_eq!(dash.subaddress_with(&rules), Some("tag"));
+    assert_eq!(EmailContact::new("user+@example.org").subaddress(), None);
+    assert_eq!(EmailContact::new("+tag@example.org").subaddress(), None);
+
+    assert!(plus
+        .clone()
+        .strip_subaddress(&rules)
+        .deep_eq(&EmailContact::new("user@example.org").set_name("User")));
+    assert_eq!(
+        plus.set_subaddress("other", &rules).email().unwrap(),
+        "user+other@example.org"
+    );
+    assert_eq!(
+        dash.strip_subaddress(&rules).email().unwrap(),
+        "user@example.net"
+    );
+
+    let quoted = EmailContact::new(r#""a+b"@x.org"#);
+    assert_eq!(quoted.subaddress(), None);
+    assert!(quoted.clone().strip_subaddress(&rules).deep_eq(&quoted));
+    assert!(quoted.clone().set_subaddress("c", &rules).deep_eq(&quoted));
+    assert!(!rules.equivalent(r#""a+b"@x.org"#, r#""a+c"@x.org"#));
+    assert_eq!(
+        EmailContact::new(r#""a+b"+tag@x.org"#)
+            .strip_subaddress(&rules)
+            .email()
+            .unwrap(),
+        r#""a+b"@x.org"#
+    );
+
+    let mut list =
+        parse_address_list("user+a@example.org, user-b@EXAMPLE.net, user@example.org").unwrap();
+    assert!(list.contains_with(&Contact::new("user+z@example.org"), &rules));
+    list.strip_subaddresses(&rules);
+    list.dedup(&CaseInsensitiveDomain);
+    assert_eq!(list.to_string(), "<user@example.org>, <user@EXAMPLE.net>");
+}
+
+#[test]
+fn provider_rules() {
+    let builtin = ProviderRules::builtin();
+    assert!(builtin.equivalent("Jane.Doe+news@GoogleMail.com", "janedoe@gmail.com"));
+    assert!(builtin.equivalent("jane+x@Outlook.com", "JANE@outlook.com"));
+    assert!(!builtin.equivalent("jane.doe@outlook.com", "janedoe@outlook.com"));
+    assert!(!builtin.equivalent("Jane@icloud.com", "jane@me.com"));
+    assert!(ProviderRules::empty().rule("gmail.com").is_none());
+
+    // Separators and dots inside quotes are part of the mailbox
+    assert_eq!(
+        builtin.canonical_address("\"a+b\"@gmail.com"),
+        "\"a+b\"@gmail.com"
+    );
+    assert_eq!(
+        builtin.canonical_address("\"J.Doe\".x+y@googlemail.com"),
+        "\"j.doe\"x@gmail.com"
+    );
+    assert!(!ProviderAware.equivalent("\"a+b\"@gmail.com", "a@gmail.com"));
+
+    // Replacing the Gmail rule drops the old rule's alias as well
+    let replaced = ProviderRules::builtin().set_rule(ProviderRule::new("gmail.com"));
+    assert!(replaced.rule("googlemail.com").is_none());
+    assert_eq!(
+        replaced.canonical_address("Jane.Doe+news@gmail.com"),
+        "Jane.Doe+news@gmail.com"
+    );
+    assert_eq!(
+        replaced.canonical_address("Jane.Doe+news@googlemail.com"),
+        "Jane.Doe+news@googlemail.com"
+    );
+    assert!(replaced.equivalent("jane+x@outlook.com", "jane@outlook.com"));
+
+    let merged = ProviderRules::builtin()
+        .set_rule(ProviderRule::new("googlemail.com").set_aliases(&["gmail.com"]));
+    assert!(merged.equivalent("Jane@gmail.com", "Jane@GOOGLEMAIL.com"));
+    assert!(!merged.equivalent("jane@gmail.com", "Jane@gmail.com"));
+
+    let contact = EmailContact::new("Jane.Doe+crm@gmail.com").set_name("Jane");
+    assert!(canonicalize(&contact, &builtin)
+        .deep_eq(&EmailContact::new("janedoe@gmail.com").set_name("Jane")));
+    assert!(canonicalize(&contact, &replaced).deep_eq(&contact));
+}
+
+#[test]
+fn idna_conversion() {
+    let list =
+        parse_address_list("a@bücher.example, b@[192.0.2.1], c@xn--mnchen-3ya.example").unwrap();
+    let ascii = list.to_ascii_domains().unwrap();
+    let unicode = ascii.to_unicode_domains().unwrap();
+    assert_eq!(
+        ascii.contacts()[0].email().unwrap(),
+        "a@xn--bcher-kva.example"
+    );
+    assert_eq!(
+        ascii.contacts()[2].email().unwrap(),
+        "c@xn--mnchen-3ya.example"
+    );
+    assert_eq!(unicode.contacts()[0].email().unwrap(), "a@bücher.example");
+    assert_eq!(unicode.contacts()[2].email().unwrap(), "c@münchen.example");
+
+    let invalid = parse_address_list("ok@example.org, broken@xn--invalid-.example").unwrap();
+    match invalid.to_unicode_domains() {
+        Err(Error::InvalidDomain(d)) => assert_eq!(d, "xn--invalid-.example"),
+        other => panic!("{:?}", other),
+    }
+
+    let unsplittable = parse_address_list("ok@example.org, a@b@c.org").unwrap();
+    match unsplittable.to_ascii_domains() {
+        Err(Error::InvalidDomain(d)) => assert_eq!(d, "a@b@c.org"),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn address_parts() {
+    let quoted = EmailContact::new(r#""a@b"+tag@Example.org"#);
+    assert_eq!(quoted.local_part(), Some(r#""a@b"+tag"#));
+    assert_eq!(quoted.local_part_unquoted().unwrap(), "a@b+tag");
+    assert_eq!(quoted.domain(), Some("Example.org"));
+    assert_eq!(quoted.subaddress(), Some("tag"));
+    assert!(!quoted.is_domain_literal());
+
+    let apostrophe = EmailContact::new("o'brien@example.org");
+    assert_eq!(apostrophe.local_part(), Some("o'brien"));
+
+    for literal in ["postmaster@[192.0.2.1]", "postmaster@[IPv6:2001:db8::1]"] {
+        let contact = EmailContact::new(literal);
+        assert_eq!(contact.local_part(), Some("postmaster"));
+        assert!(contact.is_domain_literal());
+    }
+
+    let broken = EmailContact::new("no-domain@");
+    assert_eq!(broken.local_part(), None);
+    assert_eq!(broken.domain(), None);
+    assert_eq!(EmailContact::new("a@b@c").domain(), None);
+}
+
+#[test]
+fn validation_levels() {
+    use ValidationLevel::*;
+
+    let valid = EmailContact::new("user.name+tag@example.org");
+    for level in [Permissive, Rfc5322Syntax, Rfc5321Deliverable, Html5Input] {
+        assert!(valid.is_valid(level), "{:?}", level);
+    }
+
+    let unicode = EmailContact::new("ríomhphost@bücher.example");
+    assert!(unicode.is_valid(Rfc5322Syntax));
+    assert!(unicode.is_valid(Rfc5321Deliverable));
+    assert_eq!(
+        unicode.validate(Html5Input),
+        vec![Violation::InvalidLocalPart, Violation::InvalidDomain]
+    );
+
+    let quoted = EmailContact::new(r#""with space"@example.org"#);
+    assert!(quoted.is_valid(Rfc5322Syntax));
+    assert_eq!(
+        quoted.validate(Html5Input),
+        vec![Violation::InvalidLocalPart]
+    );
+
+    let dots = EmailContact::new("two..dots@example.org");
+    assert!(dots.is_valid(Permissive));
+    assert_eq!(
+        dots.validate(Rfc5322Syntax),
+        vec![Violation::InvalidLocalPart]
+    );
+    assert!(dots.is_valid(Html5Input));
+
+    let label = "l".repeat(64);
+    let long = EmailContact::new(format!("user@{}.example", label));
+    assert!(long.is_valid(Rfc5322Syntax));
+    assert_eq!(
+        long.validate(Rfc5321Deliverable),
+        vec![Violation::LabelTooLong(label.clone())]
+    );
+    assert_eq!(
+        long.validate(Html5Input),
+        vec![Violation::LabelTooLong(label)]
+    );
+
+    let address = EmailContact::new(format!(
+        "{}@{}.example",
+        "u".repeat(20),
+        ["a"; 120].join(".")
+    ));
+    assert_eq!(
+        address.validate(Rfc5321Deliverable),
+        vec![Violation::AddressTooLong(268)]
+    );
+
+    let hyphen = EmailContact::new("user@-example.org");
+    assert!(hyphen.is_valid(Rfc5322Syntax));
+    assert_eq!(
+        hyphen.validate(Rfc5321Deliverable),
+        vec![Violation::InvalidDomain]
+    );
+
+    for literal in ["postmaster@[192.0.2.1]", "postmaster@[IPv6:2001:db8::1]"] {
+        assert!(EmailContact::new(literal).is_valid(Rfc5321Deliverable));
+    }
+    assert_eq!(
+        EmailContact::new("postmaster@[300.0.2.1]").validate(Rfc5321Deliverable),
+        vec![Violation::InvalidAddressLiteral]
+    );
+
+    assert_eq!(
+        EmailContact::new("nope").validate(Permissive),
+        vec![Violation::Unparseable]
+    );
+}
+
+#[test]
+fn address_literals() {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    let list = parse_address_list(
+        "postmaster@[192.0.2.1], MTA <test@[IPv6:2001:db8::1]>, x@[tag:content], y@[999.0.0.1]",
+    )
+    .unwrap();
+    let manual: AddressList = vec![
+        Contact::new("postmaster@[192.0.2.1]"),
+        Contact::new("test@[IPv6:2001:db8::1]").set_name("MTA"),
+        Contact::new("x@[tag:content]"),
+        Contact::new("y@[999.0.0.1]"),
+    ]
+    .into();
+    assert!(list.deep_eq(&manual), "{:?}", list);
+
+    let literals: Vec<_> = list
+        .contacts()
+        .iter()
+        .map(|c| match c {
+            Contact::Email(e) => e.address_literal(),
+            Contact::Garbage(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        literals[0],
+        Some(AddressLiteral::Ip(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1))))
+    );
+    assert_eq!(
+        literals[1].as_ref().unwrap().to_string(),
+        "[IPv6:2001:db8::1]"
+    );
+    assert_eq!(
+        literals[2],
+        Some(AddressLiteral::General {
+            tag: "tag".into(),
+            content: "content".into()
+        })
+    );
+    assert_eq!(literals[3], None);
+}
+
+#[test]
+fn source_routes() {
+    let mut list = parse_address_list(
+        "<@relay1,@relay2:user@example.org>, Name <@[192.0.2.1]:other@example.org>, plain@example.org",
+    )
+    .unwrap();
+    let routed: AddressList = vec![
+        EmailContact::new("user@example.org")
+            .set_route(["relay1", "relay2"])
+            .into(),
+        EmailContact::new("other@example.org")
+            .set_name("Name")
+            .set_route(["[192.0.2.1]"])
+            .into(),
+        Contact::new("plain@example.org"),
+    ]
+    .into();
+    assert!(list.deep_eq(&routed), "{:?}", list.deep_diff(&routed));
+
+    list.strip_routes();
+    let stripped: AddressList = vec![
+        Contact::new("user@example.org"),
+        Contact::new("other@example.org").set_name("Name"),
+        Contact::new("plain@example.org"),
+    ]
+    .into();
+    assert_eq!(
+        routed.deep_diff(&stripped),
+        vec![
+            Difference::Contact(0, vec![Difference::Route]),
+            Difference::Contact(1, vec![Difference::Route]),
+        ]
+    );
+    assert!(list.deep_eq(&stripped));
+    assert_eq!(list, routed);
+}
+
+#[test]
+fn mailto_round_trip() {
+    let mailto = Mailto {
+        to: parse_address_list("Name <a@x.org>, bücher@y.org").unwrap(),
+        cc: parse_address_list("c@z.org").unwrap(),
+        bcc: parse_address_list("d+tag@z.org").unwrap(),
+        hfields: vec![
+            ("subject".into(), "Q&A = 100% fun?".into()),
+            ("body".into(), "Line one\r\nLine two".into()),
+        ],
+    };
+    let uri = mailto.to_string();
+    assert_eq!(
+        uri,
+        concat!(
+            "mailto:a@x.org,b%C3%BCcher@y.org?cc=c@z.org&bcc=d+tag@z.org",
+            "&subject=Q%26A%20%3D%20100%25%20fun%3F&body=Line%20one%0D%0ALine%20two",
+        )
+    );
+
+    let parsed = parse_mailto(&uri).unwrap();
+    assert!(parsed
+        .to
+        .deep_eq(&vec![Contact::new("a@x.org"), Contact::new("bücher@y.org")].into()));
+    assert!(parsed.cc.deep_eq(&mailto.cc));
+    assert!(parsed.bcc.deep_eq(&mailto.bcc));
+    assert_eq!(parsed.hfields, mailto.hfields);
+
+    let merged = parse_mailto("MAILTO:?to=a@x.org&To=b@y.org&cc=").unwrap();
+    assert_eq!(merged.to.len(), 2);
+    assert!(merged.cc.is_empty());
+
+    // Garbage doesn't leave an empty hfield behind
+    let garbage = parse_mailto("mailto:a@b.org?cc=garbage&bcc=c@d.org").unwrap();
+    assert_eq!(garbage.to_string(), "mailto:a@b.org?bcc=c@d.org");
+    let garbage = parse_mailto("mailto:a@b.org?cc=garbage").unwrap();
+    assert_eq!(garbage.to_string(), "mailto:a@b.org");
+
+    assert!(matches!(
+        parse_mailto("https://example.org"),
+        Err(Error::InvalidUri(_))
+    ));
+    assert!(matches!(
+        parse_mailto("mailto:%FF@x.org"),
+        Err(Error::InvalidUri(_))
+    ));
+}
+
+#[test]
+fn extract_from_text() {
+    let text = concat!(
+        "Write to \"Doe, Jane\" <jane@x.org>, cc Support Team (support@y.org) and ",
+        "anyone@z.org. Not user@localhost though. Or ask ríomhphost [at] example (dot) ie!"
+    );
+    let found = extract_contacts(text, &ExtractOptions::default());
+    let contacts: Vec<_> = found.iter().map(|f| f.contact.clone()).collect();
+    assert_eq!(contacts.len(), 3);
+    assert!(contacts[0].deep_eq(&EmailContact::new("jane@x.org").set_name("Doe, Jane")));
+    assert!(contacts[1].deep_eq(&EmailContact::new("support@y.org").set_name("Support Team")));
+    assert!(contacts[2].deep_eq(&EmailContact::new("anyone@z.org")));
+    assert_eq!(&text[found[0].span.clone()], "\"Doe, Jane\" <jane@x.org>");
+    assert_eq!(&text[found[1].address_span.clone()], "support@y.org");
+    assert_eq!(&text[found[2].span.clone()], "anyone@z.org");
+    assert!(found.iter().all(|f| !f.deobfuscated));
+
+    let options = ExtractOptions::new().set_names(false).set_deobfuscate(true);
+    let found = extract_contacts(text, &options);
+    assert_eq!(found.len(), 4);
+    assert!(found[1].contact.name().is_none());
+    assert!(found[3].deobfuscated);
+    assert_eq!(found[3].contact.email().unwrap(), "ríomhphost@example.ie");
+    assert_eq!(
+        &text[found[3].span.clone()],
+        "ríomhphost [at] example (dot) ie"
+    );
+
+    // Apostrophes belong to the local part, surrounding quotes don't
+    let single = |text: &str| {
+        let found = extract_contacts(text, &ExtractOptions::default());
+        assert_eq!(found.len(), 1, "{}", text);
+        found.into_iter().next().unwrap()
+    };
+    let found = single("write to o'brien@example.org");
+    assert_eq!(found.contact.email().unwrap(), "o'brien@example.org");
+    let found = single("Seán (sean.o'neill@example.ie)");
+    assert!(found
+        .contact
+        .deep_eq(&EmailContact::new("sean.o'neill@example.ie").set_name("Seán")));
+    let text = "«x@y.org»";
+    let found = single(text);
+    assert_eq!(found.contact.email().unwrap(), "x@y.org");
+    assert_eq!(&text[found.address_span], "x@y.org");
+    for text in ["'x@y.org'", "“x@y.org”.", "‘x@y.org’", "„x@y.org“"] {
+        assert_eq!(single(text).contact.email().unwrap(), "x@y.org");
+    }
+}
+
+#[test]
+fn deobfuscation() {
+    assert_eq!(
+        deobfuscate("jane [at] example [dot] org"),
+        "jane@example.org"
+    );
+    assert_eq!(
+        deobfuscate("hans (AT) beispiel (punkt) de"),
+        "hans@beispiel.de"
+    );
+    assert_eq!(
+        deobfuscate("jean arobase exemple point fr"),
+        "jean@exemple.fr"
+    );
+    assert_eq!(deobfuscate("jane.NOSPAM@example.org"), "jane@example.org");
+    assert_eq!(deobfuscate("jane@nospam.example.org"), "jane@example.org");
+    assert_eq!(deobfuscate("look at that dot"), "look at that dot");
+
+    // Markers are only stripped if a real local part or domain remains
+    for unchanged in [
+        "nospam@example.org",
+        "user@nospam.org",
+        "x@remove-this.org",
+        "Meet me at home.com",
+        "Support at Example.com <support@example.com>",
+        "\"jane [at] example [dot] org\" <jane@example.org>",
+    ] {
+        assert_eq!(deobfuscate(unchanged), unchanged);
+    }
+    assert_eq!(deobfuscate("jane at example.org"), "jane@example.org");
+    assert_eq!(
+        deobfuscate("Jane <jane at example.org>, joe (at) example.org"),
+        "Jane <jane@example.org>, joe@example.org"
+    );
+    assert_eq!(
+        deobfuscate("x.remove-me@mail.nospam.example.org"),
+        "x@mail.example.org"
+    );
+
+    let options = ParseOptions::new().set_deobfuscate(true);
+    let list = parse_address_list_with(
+        "Jane <jane {at} example {dot} org>, joe@remove-this.example.org, x@y.org",
+        &options,
+    )
+    .unwrap();
+    let emails: Vec<_> = list
+        .contacts()
+        .iter()
+        .map(|c| match c {
+            Contact::Email(e) => (e.email().unwrap().clone(), e.is_deobfuscated()),
+            Contact::Garbage(_) => unreachable!(),
+        })
+        .collect();
+    assert_eq!(
+        emails,
+        vec![
+            ("jane@example.org".to_string(), true),
+            ("joe@example.org".to_string(), true),
+            ("x@y.org".to_string(), false),
+        ]
+    );
+
+    let flags = |input: &str| -> Vec<bool> {
+        parse_address_list_with(input, &options)
+            .unwrap()
+            .contacts()
+            .iter()
+            .map(|c| match c {
+                Contact::Email(e) => e.is_deobfuscated(),
+                Contact::Garbage(_) => unreachable!(),
+            })
+            .collect()
+    };
+    assert_eq!(
+        flags("jane at example dot org, jane@example.org"),
+        vec![true, false]
+    );
+    assert_eq!(
+        flags("jane@example.org, jane at example dot org"),
+        vec![false, true]
+    );
+    assert_eq!(
+        flags("\"jane@example.org\" <jane at example dot org>"),
+        vec![true]
+    );
+    assert_eq!(
+        flags("a@nospam.example.org, a@example.org"),
+        vec![true, false]
+    );
+
+    let list = parse_address_list_with("nospam@example.org", &options).unwrap();
+    assert!(list.deep_eq(&vec![Contact::new("nospam@example.org")].into()));
+    let list =
+        parse_address_list_with("Support at Example.com <support@example.com>", &options).unwrap();
+    assert!(list.deep_eq(
+        &vec![Contact::new("support@example.com").set_name("Support at Example.com")].into()
+    ));
+
+    let plain = parse_address_list_with("jane [at] example [dot] org", &ParseOptions::default());
+    assert!(plain.map_or(true, |l| l.contacts().iter().all(|c| match c {
+        Contact::Email(e) => !e.is_deobfuscated(),
+        Contact::Garbage(_) => true,
+    })));
+}
+
+#[test]
+fn homograph_detection() {
+    let spoof = EmailContact::new("s\u{0435}curity@example.org").set_name("Securit\u{0443} Team");
+    assert_eq!(
+        spoof.homographs(),
+        vec![
+            Homograph::MixedScript(ContactPart::LocalPart),
+            Homograph::Confusable {
+                part: ContactPart::LocalPart,
+                skeleton: "security".into(),
+            },
+            Homograph::MixedScript(ContactPart::Name),
+            Homograph::Confusable {
+                part: ContactPart::Name,
+                skeleton: "Security Tearn".into(),
+            },
+        ]
+    );
+    assert!(spoof.is_confusable_with(&EmailContact::new("security@EXAMPLE.org")));
+
+    // Single-script non-Latin text isn't suspicious by itself
+    let greek = EmailContact::new("αλφα@παράδειγμα.ελ").set_name("Άλφα Βήτα");
+    assert!(greek.homographs().is_empty());
+    // Neither is mixing scripts across labels or words
+    let mixed = EmailContact::new("info@例え.example").set_name("田中 Tanaka");
+    assert!(mixed.homographs().is_empty());
+
+    // Nor is typography in otherwise ASCII text
+    for typographic in [
+        EmailContact::new("jane@example.org").set_name("Jane O\u{2019}Brien"),
+        EmailContact::new("anne@example.org").set_name("Anne\u{2013}Marie"),
+        EmailContact::new("jane@example.org").set_name("Jane\u{00A0}Doe"),
+        EmailContact::new("o\u{2019}brien@example.org"),
+    ] {
+        assert!(typographic.homographs().is_empty(), "{:?}", typographic);
+    }
+    // Unless there's a lookalike letter as well
+    let spoof = EmailContact::new("jane@example.org").set_name("J\u{0430}ne O\u{2019}Brien");
+    assert!(spoof.homographs().iter().any(|h| matches!(
+        h,
+        Homograph::Confusable {
+            part: ContactPart::Name,
+            ..
+        }
+    )));
+
+    assert!(EmailContact::new("nonsense").skeleton().is_none());
+    assert!(!EmailContact::new("a@b.org").is_confusable_with(&EmailContact::new("nonsense")));
+}
+
+#[test]
+fn display_name_spoofing() {
+    let options = SpoofingOptions::new()
+        .set_protected_name(ProtectedName::new("PayPal").set_domains(&["paypal.com"]));
+    let check = |input: &str| match parse_contact(input).unwrap() {
+        Contact::Email(e) => e.spoofing(&options),
+        Contact::Garbage(_) => unreachable!(),
+    };
+
+    assert_eq!(
+        check(r#""billing (at) paypal (dot) com" <x@evil.example>"#),
+        vec![
+            Spoofing::AddressInName("billing@paypal.com".into()),
+            Spoofing::ProtectedName("PayPal".into()),
+        ]
+    );
+    assert_eq!(
+        check("\"Support team, Example.org\" <help@evil.example>"),
+        vec![Spoofing::DomainInName("Example.org".into())]
+    );
+    assert_eq!(
+        check("\"\u{03a1}aypal Service\" <service@evil.example>"),
+        vec![Spoofing::ProtectedName("PayPal".into())]
+    );
+    // Words merely containing a protected name are fine
+    assert!(check("PayPalooza <fan@example.org>").is_empty());
+    // So is the genuine article and mentioning one's own address or domain
+    assert!(check("PayPal <service@intl.paypal.com>").is_empty());
+    assert!(check("\"JANE@example.org\" <jane@example.org>").is_empty());
+    assert!(check("\"Jane at example.org\" <jane@lists.example.org>").is_empty());
+    assert!(check("\"Support at example.org\" <help@example.org>").is_empty());
+    assert!(check("\"jane.doe\" <jane.doe@example.org>").is_empty());
+    assert!(check("\"jane.dev\" <jane.dev@example.org>").is_empty());
+    assert!(check("\"node.js team\" <team@example.org>").is_empty());
+    assert!(check("Mr.Smith <smith@example.org>").is_empty());
+    assert_eq!(
+        check("\"Help at shop.example.de\" <help@evil.example>"),
+        vec![Spoofing::AddressInName("Help@shop.example.de".into())]
+    );
+    assert_eq!(
+        check("\"Support at example.org\" <help@evil.example>"),
+        vec![Spoofing::AddressInName("Support@example.org".into())]
+    );
+    assert!(check("jane@example.org").is_empty());
+}
+
+#[test]
+fn invisible_characters() {
+    let mut list: AddressList = Group::new("Fo\u{200B}ireann")
+        .set_contacts(vec![
+            Contact::new("ainm\u{200D}@example.org").set_name("\u{2066}\u{2069}"),
+            Contact::new("eile@example.org").set_comment("nóta\u{202E}"),
+            GarbageContact::new("bruscar\u{FEFF}").to_contact(),
+        ])
+        .into();
+
+    assert_eq!(
+        format!("{:#}", list),
+        "\"Fo\\u{200B}ireann\": \"\\u{2066}\\u{2069}\" <ainm\\u{200D}@example.org>, <eile@example.org>;"
+    );
+    let found = list.invisible_chars();
+    let fields: Vec<_> = found.iter().map(|i| (i.contact, i.field)).collect();
+    assert_eq!(
+        fields,
+        vec![
+            (None, Field::GroupName),
+            (Some(0), Field::Name),
+            (Some(0), Field::Name),
+            (Some(0), Field::Email),
+            (Some(1), Field::Comment),
+            (Some(2), Field::Garbage),
+        ]
+    );
+
+    let mut escaped = list.clone();
+    assert_eq!(escaped.sanitise(Sanitisation::Escape).len(), 6);
+    assert_eq!(escaped.contacts()[1].comment().unwrap(), "nóta\\u{202E}");
+    assert!(escaped.invisible_chars().is_empty());
+
+    assert_eq!(list.sanitise(Sanitisation::Strip), found);
+    assert_eq!(
+        list.to_string(),
+        "\"Foireann\": <ainm@example.org>, <eile@example.org>;"
+    );
+    assert_eq!(list.contacts()[0].name(), None);
+    assert_eq!(list.contacts()[2].comment().unwrap(), "bruscar");
+    assert_eq!(format!("{:#}", list), list.to_string());
+}
+
+#[test]
+fn unicode_normalisation() {
+    let input = "\"Zoe\u{308}\" (cafe\u{301}) <zoe\u{308}@bu\u{308}cher.example>, \
+                 \u{FB01}nance@example.org";
+    let composed = parse_address_list("\"Zoë\" <zoë@bücher.example>").unwrap();
+
+    let list = parse_address_list(input).unwrap();
+    assert!(!list.contacts()[0].deep_eq(&composed.contacts()[0]));
+
+    let options = ParseOptions::new().set_normalisation(NormalisationForm::Nfc);
+    let nfc = parse_address_list_with(input, &options).unwrap();
+    let first = match &nfc.contacts()[0] {
+        Contact::Email(e) => e.clone(),
+        Contact::Garbage(_) => unreachable!(),
+    };
+    assert_eq!(first.normalisation(), Some(NormalisationForm::Nfc));
+    assert_eq!(first.name().unwrap(), "Zoë");
+    assert_eq!(first.comment().unwrap(), "café");
+    assert_eq!(first.email().unwrap(), "zoë@bücher.example");
+    assert_eq!(
+        nfc.contacts()[1].email().unwrap(),
+        "\u{FB01}nance@example.org"
+    );
+
+    let mut nfkc = list.clone();
+    nfkc.normalise(NormalisationForm::Nfkc);
+    assert_eq!(nfkc.contacts()[1].email().unwrap(), "finance@example.org");
+
+    let mut group: AddressList = Group::new("Ｔｅａｍ").into();
+    group.normalise(NormalisationForm::Nfkc);
+    assert_eq!(group.group_name().unwrap(), "Team");
+
+    // Without normalising first, the decomposed duplicate isn't recognised
+    let mut contacts = list.contacts().clone();
+    contacts.merge_preferring_names(composed.contacts(), &CaseInsensitiveDomain);
+    assert_eq!(contacts.len(), 3);
+    let mut contacts = list.contacts().clone();
+    contacts.merge_preferring_names(composed.contacts(), &NormalisationForm::Nfc);
+    assert_eq!(contacts.len(), 2);
+}
+
+#[test]
+fn name_analysis() {
+    let parts = |name: &str| {
+        EmailContact::new("x@example.org")
+            .set_name(name)
+            .name_parts()
+    };
+    let natural = |name: &str| {
+        EmailContact::new("x@example.org")
+            .set_name(name)
+            .display_name_natural_order()
+    };
+
+    let contact = match parse_contact("Last Name, First Name <x@y.org>").unwrap() {
+        Contact::Email(e) => e,
+        Contact::Garbage(_) => unreachable!(),
+    };
+    let p = contact.name_parts().unwrap();
+    assert!(p.comma_inverted);
+    assert_eq!(p.family.unwrap(), "Last Name");
+    assert_eq!(p.given.unwrap(), "First Name");
+    assert_eq!(
+        contact.display_name_natural_order().unwrap(),
+        "First Name Last Name"
+    );
+
+    let p = parts("Prof. Dr. Ludwig van Beethoven III").unwrap();
+    assert_eq!(p.prefix.unwrap(), "Prof. Dr.");
+    assert_eq!(p.given.unwrap(), "Ludwig");
+    assert_eq!(p.family.unwrap(), "van Beethoven");
+    assert_eq!(p.suffix.unwrap(), "III");
+    assert!(!p.comma_inverted);
+
+    let p = parts("Martin Luther King, Jr.").unwrap();
+    assert_eq!(p.given.unwrap(), "Martin Luther");
+    assert_eq!(p.family.unwrap(), "King");
+    assert_eq!(p.suffix.unwrap(), "Jr.");
+    assert!(!p.comma_inverted);
+    assert_eq!(
+        natural("Martin Luther King, Jr.").unwrap(),
+        "Martin Luther King, Jr."
+    );
+
+    let p = parts("Madonna").unwrap();
+    assert_eq!(p.given.unwrap(), "Madonna");
+    assert_eq!(p.family, None);
+
+    let p = parts("王小明").unwrap();
+    assert!(p.ideographic);
+    assert_eq!(p.family.unwrap(), "王");
+    assert_eq!(p.given.unwrap(), "小明");
+    assert_eq!(natural("王小明").unwrap(), "王小明");
+    let p = parts("김민준").unwrap();
+    assert_eq!(
+        (p.family.unwrap(), p.given.unwrap()),
+        ("김".into(), "민준".into())
+    );
+
+    assert_eq!(parts("a, b, c"), None);
+    assert_eq!(parts("jane@example.org"), None);
+    assert_eq!(natural("a, b, c").unwrap(), "a, b, c");
+    assert_eq!(EmailContact::new("x@example.org").name_parts(), None);
+    assert_eq!(
+        EmailContact::new("x@example.org").display_name_natural_order(),
+        None
+    );
+}
+
+#[test]
+fn name_inference() {
+    let infer = |email: &str| EmailContact::new(email).infer_name();
+
+    assert_eq!(infer("JANE.DOE@example.org").unwrap(), "Jane Doe");
+    assert_eq!(
+        infer("mary-jane.mcGregor2@example.org").unwrap(),
+        "Mary Jane McGregor"
+    );
+    assert_eq!(infer("zoë@example.org").unwrap(), "Zoë");
+    assert_eq!(infer("\"jane doe\"@example.org"), None);
+    assert_eq!(infer("no-reply@example.org"), None);
+    assert_eq!(infer("sales.team@example.org"), None);
+    assert_eq!(infer("bcdfghjk@example.org"), None);
+    assert_eq!(infer("a.b.c.d.e@example.org"), None);
+    assert_eq!(infer("nonsense"), None);
+    // Nothing left once trailing digits are gone
+    assert_eq!(infer("123@x.org"), None);
+    assert_eq!(infer("jane.2@x.org").unwrap(), "Jane");
+    let contact = EmailContact::new("123@x.org").with_inferred_name();
+    assert_eq!(contact.name(), None);
+    assert!(!contact.is_name_inferred());
+
+    let contact = EmailContact::new("jane.doe@example.org").with_inferred_name();
+    assert_eq!(contact.name().unwrap(), "Jane Doe");
+    assert!(contact.is_name_inferred());
+    assert!(contact.deep_eq(&EmailContact::new("jane.doe@example.org").set_name("Jane Doe")));
+
+    let contact = contact.set_name("Jane Q. Doe");
+    assert!(!contact.is_name_inferred());
+
+    let contact = EmailContact::new("jane.doe@example.org")
+        .set_name("JD")
+        .with_inferred_name();
+    assert_eq!(contact.name().unwrap(), "JD");
+    assert!(!contact.is_name_inferred());
+}
+
+#[test]
+fn address_classification() {
+    use AddressCategory::*;
+    let categories = |email: &str| EmailContact::new(email).classify();
+
+    assert_eq!(categories("do-not-reply@example.org"), vec![NoReply]);
+    assert_eq!(categories("billing.noreply@example.org"), vec![NoReply]);
+    assert_eq!(categories("\"no_reply\"@example.org"), vec![NoReply]);
+    assert_eq!(categories("Postmaster@example.org"), vec![RoleAccount]);
+    assert_eq!(categories("abuse@example.org"), vec![RoleAccount]);
+    assert_eq!(categories("bounces+123@example.org"), vec![Bounce]);
+    assert_eq!(categories("prvs=1234abcd=jane@example.org"), vec![Bounce]);
+    assert_eq!(categories("dev-request@example.org"), vec![MailingList]);
+    assert_eq!(categories("owner-dev@example.org"), vec![MailingList]);
+    assert_eq!(
+        categories("team@googlegroups.com"),
+        vec![RoleAccount, MailingList]
+    );
+    assert!(categories("jane.doe@example.org").is_empty());
+    assert!(categories("reply@example.org").is_empty());
+    assert!(categories("supporters@example.org").is_empty());
+
+    let rules = ClassificationRules::new()
+        .set_rule(ClassificationRule::new(RoleAccount, "^jobs@").unwrap());
+    assert_eq!(
+        EmailContact::new("jobs@example.org").classify_with(&rules),
+        vec![RoleAccount]
+    );
+    assert!(EmailContact::new("noreply@example.org")
+        .classify_with(&rules)
+        .is_empty());
+    assert!(matches!(
+        ClassificationRule::new(NoReply, "[unclosed"),
+        Err(Error::InvalidPattern(_))
+    ));
+
+    let input = "MAILER-DAEMON@example.org, Jane <jane@example.org>, \
+                 support@example.org, garbage, list-bounces+jane=example.org@example.org";
+    let mut list = parse_address_list(input).unwrap();
+    list.mark_classified(&ClassificationRules::default());
+    let marks: Vec<_> = list
+        .contacts()
+        .iter()
+        .filter_map(|c| match c {
+            Contact::Email(e) => Some(e.marked_categories().to_vec()),
+            Contact::Garbage(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        marks,
+        vec![vec![Bounce], vec![], vec![RoleAccount], vec![Bounce]]
+    );
+
+    list.drop_classified(&ClassificationRules::default(), &[NoReply, Bounce]);
+    assert_eq!(list.len(), 3);
+    assert!(list.contacts()[0].deep_eq(&Contact::new("jane@example.org").set_name("Jane")));
+    assert!(list.contacts()[2].is_garbage());
+}
+
+#[test]
+fn verp_and_srs() {
+    let base = EmailContact::new("bounces@lists.x.com").set_name("Bounces");
+    let alice = Contact::new("alice+tag@example.org");
+
+    let verp = base.encode_verp(&alice).unwrap();
+    assert!(verp.deep_eq(&EmailContact::new(
+        "bounces+alice+tag=example.org@lists.x.com"
+    )));
+    assert_eq!(
+        verp.decode_verp().unwrap().email().unwrap(),
+        "alice+tag@example.org"
+    );
+    assert_eq!(
+        verp.classify(),
+        vec![AddressCategory::Bounce, AddressCategory::MailingList]
+    );
+
+    let dashed = Subaddressing::new("-");
+    let verp = EmailContact::new("list-old@x.com")
+        .encode_verp_with(&alice, &dashed)
+        .unwrap();
+    assert_eq!(verp.email().unwrap(), "list-alice+tag=example.org@x.com");
+    assert_eq!(
+        verp.decode_verp_with(&dashed).unwrap().email().unwrap(),
+        "alice+tag@example.org"
+    );
+
+    assert!(base
+        .encode_verp(&Contact::new("\"a b\"@example.org"))
+        .is_none());
+    assert!(EmailContact::new("bounces+alice=@x.com")
+        .decode_verp()
+        .is_none());
+    assert!(EmailContact::new("bounces+=example.org@x.com")
+        .decode_verp()
+        .is_none());
+
+    let srs = EmailContact::new("srs0+HHH=TT=example.org=a=b@forwarder.net");
+    assert_eq!(
+        srs.decode_srs().unwrap().email().unwrap(),
+        "a=b@example.org"
+    );
+    assert!(EmailContact::new("SRS0=HHH=TT=example.org@forwarder.net")
+        .decode_srs()
+        .is_none());
+    assert!(EmailContact::new("SRS1=HHH=fwd.net=HHH@forwarder.net")
+        .decode_srs()
+        .is_none());
+    assert!(EmailContact::new("srs@forwarder.net")
+        .decode_srs()
+        .is_none());
+}
+
+#[test]
+fn mailing_list_rewrites() {
+    let rewrite = |from: &str, reply_to: Option<&str>| {
+        let reply_to = reply_to.map(|r| parse_address_list(r).unwrap());
+        match parse_contact(from).unwrap() {
+            Contact::Email(e) => e.list_rewrite(reply_to.as_ref()),
+            Contact::Garbage(_) => unreachable!(),
+        }
+    };
+
+    let r = rewrite("'Jane Doe' via Group <group@googlegroups.com>", None).unwrap();
+    assert_eq!(r.list_name, "Group");
+    assert_eq!(r.name.unwrap(), "Jane Doe");
+    assert!(r.original.is_none());
+
+    let r = rewrite(
+        "\"jane at example.org via Dev\" <dev@lists.example.org>",
+        Some("dev@lists.example.org"),
+    )
+    .unwrap();
+    assert_eq!(r.name, None);
+    assert!(r
+        .original
+        .unwrap()
+        .deep_eq(&EmailContact::new("jane@example.org")));
+
+    let r = rewrite(
+        "\"Jane VIA Dev\" (jane@example.org) <dev@lists.example.org>",
+        None,
+    )
+    .unwrap();
+    assert!(r
+        .original
+        .unwrap()
+        .deep_eq(&EmailContact::new("jane@example.org").set_name("Jane")));
+
+    // Reply-To with several contacts: the list itself is skipped, the name
+    // decides between the rest
+    let r = rewrite(
+        "\"Jane via Dev\" <dev@lists.example.org>",
+        Some("dev@lists.example.org, Joe <joe@example.org>, \"Jane\" <jane@example.org>"),
+    )
+    .unwrap();
+    assert_eq!(r.original.unwrap().email().unwrap(), "jane@example.org");
+    let r = rewrite(
+        "\"Jane via Dev\" <dev@lists.example.org>",
+        Some("joe@example.org, bob@example.org"),
+    )
+    .unwrap();
+    assert!(r.original.is_none());
+
+    // The comment style from the big parsing test
+    let r = rewrite(
+        "\"3, Member\" (via example mailing list) <list@example.org>",
+        None,
+    )
+    .unwrap();
+    assert_eq!(r.list_name, "example mailing list");
+    assert_eq!(r.name.unwrap(), "3, Member");
+
+    assert!(rewrite("Octavia <octavia@example.org>", None).is_none());
+    // " via " in a name alone isn't enough
+    assert!(rewrite("\"Mario Rossi via Roma\" <mario@example.it>", None).is_none());
+    assert!(rewrite(
+        "\"Mario Rossi via Roma\" <mario@example.it>",
+        Some("Mario Rossi <MARIO@example.it>")
+    )
+    .is_none());
+    let r = rewrite(
+        "\"Mario Rossi via Roma\" <roma@example.it>",
+        Some("Mario Rossi <mario@example.it>"),
+    )
+    .unwrap();
+    assert_eq!(r.original.unwrap().email().unwrap(), "mario@example.it");
+    assert!(rewrite("Jane (work) <jane@example.org>", None).is_none());
+}
+
+#[test]
+fn public_suffixes() {
+    let contact = |email: &str| EmailContact::new(email);
+
+    assert_eq!(
+        contact("a@shop.example.xn--55qx5d")
+            .public_suffix()
+            .unwrap(),
+        "xn--55qx5d"
+    );
+    assert_eq!(
+        contact("a@shop.example.公司.cn")
+            .registrable_domain()
+            .unwrap(),
+        "example.公司.cn"
+    );
+    assert_eq!(contact("a@x.y.ck").public_suffix().unwrap(), "y.ck");
+    assert_eq!(contact("a@www.ck").registrable_domain().unwrap(), "www.ck");
+    assert_eq!(
+        contact("a@jane.github.io").registrable_domain().unwrap(),
+        "jane.github.io"
+    );
+    assert_eq!(
+        contact("a@example.unknowntld").public_suffix().unwrap(),
+        "unknowntld"
+    );
+    assert_eq!(contact("a@co.uk").registrable_domain(), None);
+    assert_eq!(contact("a@[192.0.2.1]").public_suffix(), None);
+    assert_eq!(contact("a@example..org").public_suffix(), None);
+
+    let path =
+        std::env::temp_dir().join(format!("email-address-list-psl-{}.dat", std::process::id()));
+    std::fs::write(&path, "// custom\norg\nexample.org\n").unwrap();
+    let list = PublicSuffixList::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        contact("a@b.dept.example.org")
+            .registrable_domain_with(&list)
+            .unwrap(),
+        "dept.example.org"
+    );
+    assert_eq!(
+        PublicSuffixList::builtin().registrable_domain("b.dept.example.org"),
+        contact("a@b.dept.example.org").registrable_domain()
+    );
+    assert!(matches!(
+        PublicSuffixList::from_file("/nonexistent/list.dat"),
+        Err(Error::Io(_))
+    ));
+
+    let addresses = parse_address_list(
+        "Group: a@x.example.org, garbage, b@[192.0.2.1], c@example.org, d@[192.0.2.1];",
+    )
+    .unwrap();
+    let groups = addresses.group_by_organisation();
+    let summary: Vec<_> = groups
+        .iter()
+        .map(|g| (g.name.as_str(), g.contacts.len()))
+        .collect();
+    assert_eq!(summary, vec![("example.org", 2), ("[192.0.2.1]", 2)]);
+    let groups = addresses.group_by_organisation_with(&list);
+    let summary: Vec<_> = groups
+        .iter()
+        .map(|g| (g.name.as_str(), g.contacts.len()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("x.example.org", 1), ("[192.0.2.1]", 2), ("example.org", 1)]
+    );
+}